@@ -1,18 +1,20 @@
 use askama::Template;
 use askama_web::WebTemplate;
 use axum::{
-    extract::State,
-    http::{header, HeaderMap},
-    response::{IntoResponse, Response},
+    extract::{Query, State},
+    http::{header, HeaderMap, StatusCode},
+    response::{IntoResponse, Redirect, Response},
     routing::{get, post},
-    Form, Router,
+    Form, Json, Router,
 };
-use serde::Deserialize;
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     sync::{Arc, RwLock},
-    time::SystemTime,
+    time::{Duration, Instant, SystemTime},
 };
+use tokio::sync::Semaphore;
 use uuid::Uuid;
 use wordle_word::*;
 
@@ -20,79 +22,135 @@ use wordle_word::*;
 
 struct WordData {
     available_words: Vec<String>,
+    /// Candidates that are also on the curated answer list (`WORDLE_ANSWER_LIST`), if any.
+    answer_words: Vec<String>,
+    /// Best opener against today's candidates (past answers excluded), computed once per load.
+    recommended_opener: Option<String>,
+    /// Best second guess per feedback for each precomputed opener (see `opener_tree`).
+    opener_trees: HashMap<String, HashMap<String, String>>,
+    /// Whether the last fetch of the word list found it unchanged (HTTP 304).
+    word_list_status: FetchStatus,
+    /// Past answers, newest first.
     used_words: Vec<String>,
-    commonality: HashMap<String, f64>,
+    /// Shared so ranking can run on a blocking thread without copying it.
+    commonality: Arc<HashMap<String, f64>>,
+    /// Where the data above came from, for `/about`.
+    sources: DataSources,
     loaded_at: SystemTime,
 }
 
-fn format_timestamp(t: SystemTime) -> String {
-    let secs = t
-        .duration_since(SystemTime::UNIX_EPOCH)
-        .unwrap_or_default()
-        .as_secs();
-    // Convert to date/time components (UTC)
-    let days = secs / 86400;
-    let time_secs = secs % 86400;
-    let hours = time_secs / 3600;
-    let minutes = (time_secs % 3600) / 60;
-
-    // Days since 1970-01-01 to Y-M-D
-    let mut y = 1970i64;
-    let mut remaining = days as i64;
-    loop {
-        let days_in_year = if y % 4 == 0 && (y % 100 != 0 || y % 400 == 0) {
-            366
+impl WordData {
+    /// The candidate pool a session draws from.
+    fn pool(&self, answer_pool_only: bool) -> &[String] {
+        if answer_pool_only {
+            &self.answer_words
         } else {
-            365
-        };
-        if remaining < days_in_year {
-            break;
-        }
-        remaining -= days_in_year;
-        y += 1;
-    }
-    let leap = y % 4 == 0 && (y % 100 != 0 || y % 400 == 0);
-    let month_days = [
-        31,
-        if leap { 29 } else { 28 },
-        31,
-        30,
-        31,
-        30,
-        31,
-        31,
-        30,
-        31,
-        30,
-        31,
-    ];
-    let mut m = 0;
-    for md in &month_days {
-        if remaining < *md {
-            break;
+            &self.available_words
         }
-        remaining -= md;
-        m += 1;
-    }
-    format!(
-        "{:04}-{:02}-{:02} {:02}:{:02} UTC",
-        y,
-        m + 1,
-        remaining + 1,
-        hours,
-        minutes
-    )
+    }
+}
+
+/// A play time like `2m13s`, or `45s` under a minute.
+fn format_duration(d: Duration) -> String {
+    let secs = d.as_secs();
+    if secs < 60 {
+        format!("{}s", secs)
+    } else {
+        format!("{}m{:02}s", secs / 60, secs % 60)
+    }
 }
 
 struct AppState {
     word_data: RwLock<WordData>,
     sessions: RwLock<HashMap<String, Session>>,
+    rank_options: RankOptions,
+    /// Words pinned to the top of suggestions while they remain candidates.
+    favorites: HashSet<String>,
+    /// Weight of the experimental past-answer pattern bias, if enabled.
+    past_pattern_weight: Option<f64>,
+    /// Weight of the answer positional-frequency bonus, if enabled.
+    answer_position_weight: Option<f64>,
+    /// Show suggestion scores as 0-100 relative to the best (`WORDLE_NORMALIZED_SCORES`).
+    normalized_scores: bool,
+    /// Guesses allowed per game (`WORDLE_MAX_GUESSES`, default 6).
+    max_guesses: usize,
+    /// Token required by the `/admin` routes (`WORDLE_ADMIN_TOKEN`); unset disables them.
+    admin_token: Option<String>,
+    /// Single permit held while word data is being (re)loaded.
+    reload_permit: Semaphore,
 }
 
 struct Session {
     state: GameState,
     candidates: Vec<String>,
     guesses: Vec<(String, String)>,
+    /// Candidate count at the start of the game and after each guess.
+    candidate_history: Vec<usize>,
+    /// Restrict candidates to the curated answer list instead of the full word list.
+    answer_pool_only: bool,
+    /// How many suggestions the panel shows (`GET /?limit=N`).
+    suggestion_limit: usize,
+    /// How much help the panel shows (`GET /?hints=N`).
+    hint_level: HintLevel,
+    /// Hide suggestions scoring under this percentage of the best (`GET /?min_score=N`).
+    min_score: f64,
+    /// Tile color scheme chosen for this session.
+    palette: Palette,
+    /// Full ranking from the last `/api/suggestions` call, reused while its key matches.
+    ranked: Option<CachedRanking>,
+    /// When the game started, for the play timer.
+    started_at: Instant,
+    /// When each guess was made, parallel to `guesses`.
+    guessed_at: Vec<Instant>,
+    /// Letters set directly at known positions (0-based), as with a crossword clue.
+    known_letters: Vec<(usize, char)>,
+}
+
+/// Feedback color schemes. The identifier becomes a CSS class on the page body.
+#[derive(Clone, Copy, Default, PartialEq)]
+enum Palette {
+    /// Wordle's green and yellow.
+    #[default]
+    Classic,
+    /// Orange and blue, like Wordle's high contrast mode.
+    HighContrast,
+    /// Blue and orange from a palette designed for color-blind readers.
+    ColorBlind,
+}
+
+impl Palette {
+    const ALL: [Palette; 3] = [Palette::Classic, Palette::HighContrast, Palette::ColorBlind];
+
+    fn id(self) -> &'static str {
+        match self {
+            Palette::Classic => "classic",
+            Palette::HighContrast => "high-contrast",
+            Palette::ColorBlind => "color-blind",
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Palette::Classic => "Classic colors",
+            Palette::HighContrast => "High contrast",
+            Palette::ColorBlind => "Color-blind friendly",
+        }
+    }
+}
+
+struct CachedRanking {
+    key: RankKey,
+    ranked: Arc<Vec<(String, f64)>>,
+}
+
+/// What a cached ranking depends on: the guesses, known letters, the candidate pool and the
+/// word data.
+#[derive(Clone, PartialEq)]
+struct RankKey {
+    guesses: Vec<(String, String)>,
+    known_letters: Vec<(usize, char)>,
+    answer_pool_only: bool,
+    loaded_at: SystemTime,
 }
 
 impl Session {
@@ -101,46 +159,217 @@ impl Session {
             state: GameState::new(),
             candidates: available_words.to_vec(),
             guesses: Vec::new(),
+            candidate_history: vec![available_words.len()],
+            answer_pool_only: false,
+            suggestion_limit: DEFAULT_SUGGESTION_LIMIT,
+            hint_level: HintLevel::default(),
+            min_score: 0.0,
+            palette: Palette::Classic,
+            ranked: None,
+            started_at: Instant::now(),
+            guessed_at: Vec::new(),
+            known_letters: Vec::new(),
+        }
+    }
+
+    /// Apply a guess and its feedback, recording when it was made.
+    fn apply_guess(&mut self, guess: String, feedback: String) {
+        self.state.update(&guess, &feedback);
+        self.candidates.retain(|w| self.state.matches(w));
+        self.guesses.push((guess, feedback));
+        self.candidate_history.push(self.candidates.len());
+        self.guessed_at.push(Instant::now());
+    }
+
+    /// Time from the start of the game to the latest guess.
+    fn play_time(&self) -> Duration {
+        self.guessed_at
+            .last()
+            .map_or(Duration::ZERO, |t| t.duration_since(self.started_at))
+    }
+
+    /// Roll the game back to just after the `to`th guess, replaying the kept
+    /// guesses from a fresh state against `pool`.
+    fn rollback(&mut self, to: usize, pool: &[String]) -> Result<(), String> {
+        if to > self.guesses.len() {
+            return Err(format!(
+                "Cannot roll back to guess {to}: only {} guesses made.",
+                self.guesses.len()
+            ));
+        }
+
+        self.guesses.truncate(to);
+        self.guessed_at.truncate(to);
+        self.state = GameState::new();
+        for &(pos, letter) in &self.known_letters {
+            self.state.set_green(pos, letter).ok();
         }
+        // The history starts from the whole pool, before any known letters
+        self.candidate_history = vec![pool.len()];
+        self.candidates = pool.to_vec();
+        self.candidates.retain(|w| self.state.matches(w));
+        for (guess, feedback) in &self.guesses {
+            self.state.update(guess, feedback);
+            self.candidates.retain(|w| self.state.matches(w));
+            self.candidate_history.push(self.candidates.len());
+        }
+        Ok(())
     }
 }
 
 type SharedState = Arc<AppState>;
 
 fn load_word_data() -> WordData {
-    let all = match all_words() {
+    let (all, word_list_status) = match fetch_word_list() {
         Ok(w) => w,
         Err(e) => {
             eprintln!("{}", e);
             return WordData {
                 available_words: Vec::new(),
+                answer_words: Vec::new(),
+                recommended_opener: None,
+                opener_trees: HashMap::new(),
+                word_list_status: FetchStatus::Downloaded,
                 used_words: Vec::new(),
-                commonality: HashMap::new(),
+                commonality: Arc::new(HashMap::new()),
+                sources: DataSources::configured(),
                 loaded_at: SystemTime::now(),
             };
         }
     };
 
-    let used = used_words();
-    let available: std::collections::HashSet<&String> = all.difference(&used).collect();
+    let dated = used_words_dated();
+    let used: HashSet<String> = dated.iter().map(|a| a.word.clone()).collect();
+    // WORDLE_RECENT_DAYS narrows the exclusion; the past-answer statistics still use all
+    let recent_days = std::env::var("WORDLE_RECENT_DAYS").ok().and_then(|value| {
+        value
+            .parse::<u64>()
+            .inspect_err(|_| eprintln!("Warning: ignoring invalid WORDLE_RECENT_DAYS: {}", value))
+            .ok()
+    });
+    let excluded = match recent_days {
+        Some(days) => recent_answers(&dated, days, today()).unwrap_or_else(|| {
+            eprintln!("Warning: past answers have no dates, so all of them are excluded.");
+            used.clone()
+        }),
+        None => used.clone(),
+    };
+    let available: HashSet<&String> = all.difference(&excluded).collect();
 
-    let freq_data = load_frequency_data(&available);
+    let freq_data = load_frequency_data(&frequency_sources(), &available);
 
     let mut available_words: Vec<String> = available.into_iter().cloned().collect();
     let plurals_removed = filter_regular_plurals(&mut available_words, &freq_data.dictionary);
 
+    // Re-read on every load, so edits to the file take effect on reload
+    let never_answers = match std::env::var("WORDLE_NEVER_ANSWERS") {
+        Ok(path) => match std::fs::read_to_string(&path) {
+            Ok(content) => parse_word_list(&content).0,
+            Err(e) => {
+                eprintln!("Warning: couldn't read never-answers file {}: {}", path, e);
+                HashSet::new()
+            }
+        },
+        Err(_) => HashSet::new(),
+    };
+    let never_removed = filter_never_answers(&mut available_words, &never_answers);
+
+    let before_known = available_words.len();
+    let known_words = match std::env::var("WORDLE_KNOWN_WORDS") {
+        Ok(path) => match std::fs::read_to_string(&path) {
+            Ok(content) => Some(parse_word_list(&content).0),
+            Err(e) => {
+                eprintln!("Warning: couldn't read known-words file {}: {}", path, e);
+                None
+            }
+        },
+        Err(_) => None,
+    };
+    if let Some(known) = &known_words {
+        filter_known_words(&mut available_words, known);
+    }
+
     println!(
-        "{} total words, {} past answers excluded, {} regular plurals filtered, {} candidates available.",
+        "{} total words, {} past answers excluded, {} regular plurals filtered, {} never-answers excluded, {} candidates available.",
         all.len(),
-        used.len(),
+        excluded.len(),
         plurals_removed,
+        never_removed,
         available_words.len()
     );
+    if !freq_data.commonality.is_empty() {
+        let refs: Vec<&String> = available_words.iter().collect();
+        println!(
+            "Commonality data covers {:.0}% of candidates.",
+            100.0 * commonality_coverage(&refs, &freq_data.commonality)
+        );
+    }
+    if known_words.is_some() {
+        println!(
+            "Known-words filter: {} candidates narrowed to {}.",
+            before_known,
+            available_words.len()
+        );
+    }
+
+    let answer_words = match std::env::var("WORDLE_ANSWER_LIST") {
+        Ok(path) => match read_word_source(&path) {
+            Ok((content, _)) => {
+                let answers = parse_word_source(&content).0;
+                available_words
+                    .iter()
+                    .filter(|w| answers.contains(*w))
+                    .cloned()
+                    .collect()
+            }
+            Err(e) => {
+                eprintln!("Warning: couldn't read answer list {}: {}", path, e);
+                Vec::new()
+            }
+        },
+        Err(_) => Vec::new(),
+    };
+
+    let recommended_opener = {
+        let refs: Vec<&String> = available_words.iter().collect();
+        best_opener(&refs, &freq_data.commonality).cloned()
+    };
+
+    // Trees for the recommended opener plus those in WORDLE_TREE_OPENERS (default salet)
+    let opener_trees = {
+        let refs: Vec<&String> = available_words.iter().collect();
+        let mut openers: Vec<String> = std::env::var("WORDLE_TREE_OPENERS")
+            .unwrap_or_else(|_| "salet".to_string())
+            .split(',')
+            .map(|w| w.trim().to_ascii_lowercase())
+            .filter(|w| all.contains(w))
+            .collect();
+        openers.extend(recommended_opener.clone());
+        openers.sort();
+        openers.dedup();
+        openers
+            .into_iter()
+            .map(|opener| {
+                let tree = opener_tree(
+                    &opener,
+                    &refs,
+                    &freq_data.commonality,
+                    &RankOptions::default(),
+                );
+                (opener, tree)
+            })
+            .collect()
+    };
 
     WordData {
         available_words,
-        used_words: used.into_iter().collect(),
-        commonality: freq_data.commonality,
+        answer_words,
+        recommended_opener,
+        opener_trees,
+        word_list_status,
+        used_words: newest_first(&dated),
+        commonality: Arc::new(freq_data.commonality),
+        sources: DataSources::configured(),
         loaded_at: SystemTime::now(),
     }
 }
@@ -152,9 +381,27 @@ struct TileData {
     class: String,
 }
 
+/// One feedback the top suggestion could get, as colored tiles spelling the word.
+struct OutcomeEntry {
+    tiles: Vec<TileData>,
+    percent: String,
+    remaining: usize,
+    /// The word itself is the answer ("ggggg").
+    solved: bool,
+}
+
 struct SuggestionEntry {
     word: String,
     score: String,
+    /// The raw score, shown on hover when `score` is normalized.
+    raw_score: String,
+    favorite: bool,
+    /// Distinct letters the word would test for the first time.
+    new_letters: usize,
+    /// Whether the word is a remaining candidate, so guessing it could win outright.
+    could_win: bool,
+    /// Average candidates left after guessing it, shown at the full hint level.
+    expected_remaining: Option<String>,
 }
 
 fn build_grid_rows(guesses: &[(String, String)]) -> Vec<Vec<TileData>> {
@@ -167,6 +414,7 @@ fn build_grid_rows(guesses: &[(String, String)]) -> Vec<Vec<TileData>> {
                     let class = match fb {
                         'g' => "green".to_string(),
                         'y' => "yellow".to_string(),
+                        '?' => "unknown".to_string(),
                         _ => "grey".to_string(),
                     };
                     TileData { letter, class }
@@ -176,6 +424,25 @@ fn build_grid_rows(guesses: &[(String, String)]) -> Vec<Vec<TileData>> {
         .collect()
 }
 
+/// The alphabet as keyboard keys colored by what is known about each letter.
+fn build_alphabet(state: &GameState) -> Vec<TileData> {
+    let statuses = state.letter_statuses();
+    ('a'..='z')
+        .map(|letter| {
+            let class = match statuses[&letter] {
+                LetterStatus::Green => "green",
+                LetterStatus::Yellow => "yellow",
+                LetterStatus::Grey => "grey",
+                LetterStatus::Untested => "",
+            };
+            TileData {
+                letter,
+                class: class.to_string(),
+            }
+        })
+        .collect()
+}
+
 /// Top past answers (already-used words) that satisfy the current constraints.
 fn used_matches(
     used_words: &[String],
@@ -191,35 +458,189 @@ fn used_matches(
     ranked.into_iter().take(20).collect()
 }
 
-fn build_suggestions(ranked: &[(String, f64)]) -> Vec<SuggestionEntry> {
+/// Longest a ranking may run before the panel falls back to frequency ranking.
+const RANK_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// The server's ranking options, plus the past-answer bias if configured.
+fn rank_options_for(word_data: &WordData, app: &AppState) -> RankOptions {
+    let mut options = app.rank_options.clone();
+    if let Some(weight) = app.past_pattern_weight {
+        options.past_answer_bias = Some(PastAnswerBias::new(&word_data.used_words, weight));
+    }
+    if let Some(weight) = app.answer_position_weight {
+        // Past answers plus the curated answer list, when there is one
+        let answers = word_data.used_words.iter().chain(&word_data.answer_words);
+        options.answer_positions = Some(AnswerPositionBonus::new(answers, weight));
+    }
+    options
+}
+
+/// Suggestions the panel shows unless the page was opened with `?limit=N`.
+const DEFAULT_SUGGESTION_LIMIT: usize = 15;
+/// Most suggestions the panel will show.
+const MAX_SUGGESTION_LIMIT: usize = 100;
+/// Most outcomes the top suggestion's detail view lists.
+const TOP_OUTCOME_LIMIT: usize = 10;
+
+/// Drop words already guessed, pin favorites first and truncate a ranking to `limit` for
+/// display.
+fn finish_suggestions(
+    mut ranked: Vec<(String, f64)>,
+    app: &AppState,
+    state: &GameState,
+    limit: usize,
+) -> Vec<(String, f64)> {
+    ranked.retain(|(w, _)| !state.guessed.contains(w));
+    pin_favorites(&mut ranked, &app.favorites);
+    ranked.truncate(limit);
+    ranked
+}
+
+/// Ranked suggestions for a session with favorites pinned first, truncated for display.
+fn top_suggestions(session: &Session, word_data: &WordData, app: &AppState) -> Vec<(String, f64)> {
+    let ranked = rank_words_owned_with(
+        &session.candidates,
+        &word_data.commonality,
+        &session.state,
+        &rank_options_for(word_data, app),
+    );
+    finish_suggestions(ranked, app, &session.state, session.suggestion_limit)
+}
+
+/// `top_suggestions` run on a blocking thread so slow strategies don't stall the runtime.
+/// If ranking exceeds `RANK_TIMEOUT`, falls back to the cheap frequency ranking and
+/// returns `true` to flag the result as approximate (the slow task finishes unobserved).
+async fn timed_suggestions(app: &SharedState, session_id: &str) -> (Vec<(String, f64)>, bool) {
+    let (inputs, commonality, options, limit) = {
+        let word_data = app.word_data.read().unwrap();
+        let sessions = app.sessions.read().unwrap();
+        let Some(session) = sessions.get(session_id) else {
+            return (Vec::new(), false);
+        };
+        (
+            Arc::new((session.candidates.clone(), session.state.clone())),
+            Arc::clone(&word_data.commonality),
+            rank_options_for(&word_data, app),
+            session.suggestion_limit,
+        )
+    };
+
+    let task = {
+        let (inputs, commonality, options) = (
+            Arc::clone(&inputs),
+            Arc::clone(&commonality),
+            options.clone(),
+        );
+        tokio::task::spawn_blocking(move || {
+            rank_words_owned_with(&inputs.0, &commonality, &inputs.1, &options)
+        })
+    };
+    if let Ok(Ok(ranked)) = tokio::time::timeout(RANK_TIMEOUT, task).await {
+        return (finish_suggestions(ranked, app, &inputs.1, limit), false);
+    }
+
+    let fallback = RankOptions {
+        strategy: Strategy::Frequency,
+        ..options
+    };
+    let ranked = rank_words_owned_with(&inputs.0, &commonality, &inputs.1, &fallback);
+    (finish_suggestions(ranked, app, &inputs.1, limit), true)
+}
+
+fn build_suggestions(
+    ranked: &[(String, f64)],
+    app: &AppState,
+    favorites: &HashSet<String>,
+    session: &Session,
+) -> Vec<SuggestionEntry> {
+    let top = ranked.iter().map(|r| r.1).fold(0.0, f64::max);
     ranked
         .iter()
         .map(|(word, score)| SuggestionEntry {
             word: word.clone(),
-            score: format!("{:.2}", score),
+            score: if app.normalized_scores {
+                format!("{:.0}", relative_score(*score, top))
+            } else {
+                format!("{:.2}", score)
+            },
+            raw_score: format!("{:.2}", score),
+            favorite: favorites.contains(word),
+            new_letters: session.state.letter_coverage(word).0,
+            could_win: session.candidates.contains(word),
+            expected_remaining: (session.hint_level == HintLevel::Full).then(|| {
+                let refs: Vec<&String> = session.candidates.iter().collect();
+                format!("{:.1}", expected_remaining(word, &refs))
+            }),
         })
         .collect()
 }
 
+/// Plain-text summary of a session: guess grid, constraints and top suggestions.
+fn build_report(session: &Session, word_data: &WordData, app: &AppState) -> String {
+    let mut lines = vec!["Wordle Solver report".to_string(), String::new()];
+
+    if session.guesses.is_empty() {
+        lines.push("No guesses yet.".to_string());
+    } else {
+        lines.push("Guesses:".to_string());
+        for (i, (word, feedback)) in session.guesses.iter().enumerate() {
+            lines.push(format!(
+                "  {}. {}  {}",
+                i + 1,
+                word.to_ascii_uppercase(),
+                feedback
+            ));
+        }
+    }
+
+    lines.push(String::new());
+    lines.push("Constraints:".to_string());
+    lines.extend(session.state.constraint_lines());
+    lines.push(format!(
+        "  Remaining candidates: {}",
+        session.candidates.len()
+    ));
+    if session.candidate_history.len() > 1 {
+        lines.push(format!(
+            "  Narrowing: {}",
+            session.candidate_history.iter().join(" -> ")
+        ));
+    }
+
+    let top = top_suggestions(session, word_data, app);
+    if !top.is_empty() {
+        lines.push(String::new());
+        lines.push("Top suggestions:".to_string());
+        for (i, (word, score)) in top.iter().enumerate() {
+            let marker = if app.favorites.contains(word) {
+                "  [favorite]"
+            } else {
+                ""
+            };
+            lines.push(format!(
+                "  {:>2}. {}  ({:.2}){}",
+                i + 1,
+                word,
+                score,
+                marker
+            ));
+        }
+    }
+
+    lines.push(String::new());
+    lines.join("\n")
+}
+
 // ---------- Templates ----------
 
 #[derive(Template, WebTemplate)]
 #[template(path = "game.html")]
 struct GameTemplate {
-    grid_rows: Vec<Vec<TileData>>,
-    guess_count: usize,
-    solved: bool,
-    no_matches: bool,
-    suggestions: Vec<SuggestionEntry>,
-    used_suggestions: Vec<SuggestionEntry>,
-    candidate_count: usize,
-    has_constraints: bool,
-    has_green: bool,
-    green_display: String,
-    required_display: String,
-    excluded_display: String,
+    results: ResultsTemplate,
+    panel: SuggestionsTemplate,
     data_loaded_at: String,
     data_stale: bool,
+    palette: Palette,
 }
 
 #[derive(Template, WebTemplate)]
@@ -227,14 +648,27 @@ struct GameTemplate {
 struct ResultsTemplate {
     grid_rows: Vec<Vec<TileData>>,
     guess_count: usize,
+    max_guesses: usize,
     solved: bool,
     no_matches: bool,
+    /// Why the last submission was rejected, if it was.
+    error: Option<String>,
+    /// Informational message, such as a lost session having been replaced.
+    notice: Option<String>,
+    /// Contradictory feedback across the guesses, if any.
+    conflict: Option<String>,
+    /// The last guess had colored tiles but didn't narrow the candidates.
+    ineffective: bool,
+    /// Time from the start of the game to the latest guess, e.g. `2m13s`.
+    play_time: String,
 }
 
 #[derive(Template, WebTemplate)]
 #[template(path = "partials/suggestions.html")]
 struct SuggestionsTemplate {
     suggestions: Vec<SuggestionEntry>,
+    /// Mark the suggestions that could win, set only when pure probes are mixed in.
+    mark_winners: bool,
     used_suggestions: Vec<SuggestionEntry>,
     candidate_count: usize,
     has_constraints: bool,
@@ -242,6 +676,45 @@ struct SuggestionsTemplate {
     green_display: String,
     required_display: String,
     excluded_display: String,
+    /// Endgame verdict when few guesses remain: `Some(Some(word))` is a guaranteed win,
+    /// `Some(None)` means no guaranteed win exists.
+    guaranteed: Option<Option<String>>,
+    guesses_left: usize,
+    /// The candidate leaving the fewest candidates on average, when the set is small enough.
+    safe_guess: Option<String>,
+    /// Most common remaining candidate and its commonality share in percent, once only a
+    /// few candidates remain (and commonality data loaded).
+    likely_answer: Option<(String, u32)>,
+    /// Set once the guess limit is reached, with the most likely answer if known.
+    out_of_guesses: Option<Option<String>>,
+    /// Shown before the first guess.
+    recommended_opener: Option<String>,
+    /// With exactly two candidates left, a guess whose feedback tells them apart.
+    disambiguator: Option<String>,
+    /// When the candidates are all anagrams, the probe that splits them best, the most
+    /// candidates it can leave and whether it could be the answer.
+    anagram_probe: Option<(String, usize, bool)>,
+    /// Untested letters that would split the candidates most evenly, e.g. "T, L, I".
+    letters_to_test: String,
+    /// Estimated guesses still needed, formatted, once there are several candidates.
+    estimated_guesses: Option<String>,
+    /// Every letter with its status, shown as a keyboard once there are constraints.
+    alphabet: Vec<TileData>,
+    /// Letter spread summary, shown once the candidates are narrowed down.
+    letter_spread: Option<String>,
+    /// The configured ranking timed out and these suggestions use frequency ranking.
+    approximate: bool,
+    /// Whether a curated answer list is loaded, so the pool toggle can be offered.
+    answer_pool_available: bool,
+    answer_pool_only: bool,
+    /// Why the last known-letter form was rejected, if it was.
+    known_letter_error: Option<String>,
+    /// The hint level hides the suggestions, leaving only the count.
+    suggestions_hidden: bool,
+    /// The top suggestion's likeliest outcomes, for the detail view.
+    top_outcomes: Vec<OutcomeEntry>,
+    /// Suggestions within the limit hidden by the session's score floor.
+    low_value_hidden: usize,
 }
 
 #[derive(Template, WebTemplate)]
@@ -251,6 +724,234 @@ struct ReloadStatusTemplate {
     message: String,
 }
 
+/// Rank a session's suggestions off the async runtime, then build its panel.
+async fn render_panel(app: &SharedState, session_id: &str) -> SuggestionsTemplate {
+    let ranked = timed_suggestions(app, session_id).await;
+    let word_data = app.word_data.read().unwrap();
+    let sessions = app.sessions.read().unwrap();
+    build_panel(sessions.get(session_id), &word_data, app, ranked)
+}
+
+/// Grid and status for a session (or an empty board when there is none).
+fn build_results(session: Option<&Session>, max_guesses: usize) -> ResultsTemplate {
+    let Some(session) = session else {
+        return ResultsTemplate {
+            grid_rows: Vec::new(),
+            guess_count: 0,
+            max_guesses,
+            solved: false,
+            no_matches: false,
+            error: None,
+            notice: None,
+            conflict: None,
+            ineffective: false,
+            play_time: String::new(),
+        };
+    };
+
+    let solved = session.guesses.last().is_some_and(|(_, fb)| fb == "ggggg");
+    let ineffective = match (&session.guesses[..], &session.candidate_history[..]) {
+        ([.., (_, feedback)], [.., before, after]) => ineffective_guess(*before, *after, feedback),
+        _ => false,
+    };
+    ResultsTemplate {
+        grid_rows: build_grid_rows(&session.guesses),
+        guess_count: session.guesses.len(),
+        max_guesses,
+        solved,
+        no_matches: session.candidates.is_empty() && !solved,
+        error: None,
+        notice: None,
+        conflict: find_letter_conflict(&session.guesses)
+            .map(|c| c.to_string())
+            .or_else(|| known_letter_conflict(&session.known_letters, &session.guesses)),
+        ineffective,
+        play_time: format_duration(session.play_time()),
+    }
+}
+
+/// Suggestions panel for a session (or an empty panel when there is none), given its
+/// ranked suggestions from `timed_suggestions`.
+fn build_panel(
+    session: Option<&Session>,
+    word_data: &WordData,
+    app: &AppState,
+    (top, approximate): (Vec<(String, f64)>, bool),
+) -> SuggestionsTemplate {
+    let Some(session) = session else {
+        return SuggestionsTemplate {
+            suggestions: Vec::new(),
+            mark_winners: false,
+            used_suggestions: Vec::new(),
+            candidate_count: 0,
+            has_constraints: false,
+            has_green: false,
+            green_display: String::new(),
+            required_display: String::new(),
+            excluded_display: String::new(),
+            guaranteed: None,
+            guesses_left: 0,
+            safe_guess: None,
+            likely_answer: None,
+            out_of_guesses: None,
+            recommended_opener: word_data.recommended_opener.clone(),
+            disambiguator: None,
+            anagram_probe: None,
+            letters_to_test: String::new(),
+            estimated_guesses: None,
+            alphabet: Vec::new(),
+            letter_spread: None,
+            approximate: false,
+            answer_pool_available: !word_data.answer_words.is_empty(),
+            answer_pool_only: false,
+            known_letter_error: None,
+            suggestions_hidden: false,
+            top_outcomes: Vec::new(),
+            low_value_hidden: 0,
+        };
+    };
+
+    let green_display = session.state.green_display();
+    let required_display = session.state.required_display();
+    let excluded_display = session.state.excluded_display();
+    let has_green = green_display != "_____";
+    let has_constraints = has_green || !required_display.is_empty() || !excluded_display.is_empty();
+
+    let used_top = used_matches(
+        &word_data.used_words,
+        &session.state,
+        &word_data.commonality,
+    );
+
+    let guesses_left = app.max_guesses.saturating_sub(session.guesses.len());
+    let guaranteed =
+        if (1..=2).contains(&guesses_left) && session.candidates.len() <= ENDGAME_SEARCH_LIMIT {
+            let refs: Vec<&String> = session.candidates.iter().collect();
+            Some(guaranteed_solution(&refs, guesses_left))
+        } else {
+            None
+        };
+
+    let safe_guess = if (2..=SAFE_GUESS_LIMIT).contains(&session.candidates.len()) {
+        let refs: Vec<&String> = session.candidates.iter().collect();
+        best_candidate_guess(&refs, &word_data.commonality).cloned()
+    } else {
+        None
+    };
+
+    let likely_answer = if (2..=LIKELY_ANSWER_LIMIT).contains(&session.candidates.len()) {
+        let refs: Vec<&String> = session.candidates.iter().collect();
+        answer_probabilities(&refs, &word_data.commonality)
+            .filter(|probs| {
+                app.rank_options
+                    .is_common(probs[0].0, &word_data.commonality)
+            })
+            .map(|probs| (probs[0].0.clone(), (probs[0].1 * 100.0).round() as u32))
+    } else {
+        None
+    };
+
+    let out_of_guesses = (session.guesses.len() >= app.max_guesses && session.candidates.len() > 1)
+        .then(|| {
+            let refs: Vec<&String> = session.candidates.iter().collect();
+            most_likely_answer(&refs, &word_data.commonality).cloned()
+        });
+
+    // `top` is ranked, so the likelier of the two is tried first
+    let disambiguator = match top.as_slice() {
+        [(a, _), (b, _)] if session.candidates.len() == 2 => disambiguating_guess(a, b, &[]),
+        _ => None,
+    };
+
+    let refs: Vec<&String> = session.candidates.iter().collect();
+    let anagram_probe = if all_anagrams(&refs) {
+        let guess_pool: Vec<&String> = word_data
+            .available_words
+            .iter()
+            .chain(&word_data.used_words)
+            .collect();
+        anagram_probe(&refs, &guess_pool)
+            .map(|(word, worst)| (word.clone(), worst, refs.contains(&word)))
+    } else {
+        None
+    };
+    let letters_to_test = letter_split_values(&refs, &session.state)
+        .iter()
+        .take(3)
+        .map(|(c, _)| c.to_ascii_uppercase())
+        .join(", ");
+    let letter_spread = (has_constraints && refs.len() > 1)
+        .then(|| letter_spread(&refs, &session.state).summary(refs.len()));
+
+    let hints = session.hint_level;
+    let shown = hints.suggestion_limit(top.len());
+    let best = top.iter().map(|r| r.1).fold(0.0, f64::max);
+    let kept: Vec<(String, f64)> = top[..shown]
+        .iter()
+        .filter(|(_, score)| meets_score_floor(*score, best, session.min_score))
+        .cloned()
+        .collect();
+    let low_value_hidden = shown - kept.len();
+    let suggestions = build_suggestions(&kept, app, &app.favorites, session);
+    let analysis = hints.shows_analysis();
+    let top_outcomes = match suggestions.first() {
+        Some(top) if analysis && refs.len() > 1 => outcome_distribution(&top.word, &refs)
+            .into_iter()
+            .take(TOP_OUTCOME_LIMIT)
+            .map(|outcome| OutcomeEntry {
+                solved: outcome.feedback == "ggggg",
+                percent: format!("{:.1}", 100.0 * outcome.probability),
+                remaining: outcome.remaining,
+                tiles: build_grid_rows(&[(top.word.clone(), outcome.feedback)]).remove(0),
+            })
+            .collect(),
+        _ => Vec::new(),
+    };
+    SuggestionsTemplate {
+        mark_winners: suggestions.iter().any(|s| !s.could_win),
+        suggestions,
+        used_suggestions: if analysis {
+            build_suggestions(&used_top, app, &HashSet::new(), session)
+        } else {
+            Vec::new()
+        },
+        candidate_count: session.candidates.len(),
+        has_constraints,
+        has_green,
+        green_display,
+        required_display,
+        excluded_display,
+        guaranteed: guaranteed.filter(|_| analysis),
+        guesses_left,
+        safe_guess: safe_guess.filter(|_| analysis),
+        likely_answer: likely_answer.filter(|_| analysis),
+        out_of_guesses: out_of_guesses.map(|answer| answer.filter(|_| analysis)),
+        disambiguator: disambiguator.filter(|_| analysis),
+        anagram_probe: anagram_probe.filter(|_| analysis),
+        letters_to_test: if analysis {
+            letters_to_test
+        } else {
+            String::new()
+        },
+        estimated_guesses: (analysis && session.candidates.len() > 1)
+            .then(|| format!("{:.1}", estimated_guesses(session.candidates.len()))),
+        alphabet: build_alphabet(&session.state),
+        letter_spread: letter_spread.filter(|_| analysis),
+        approximate,
+        recommended_opener: if analysis && session.guesses.is_empty() {
+            word_data.recommended_opener.clone()
+        } else {
+            None
+        },
+        answer_pool_available: !word_data.answer_words.is_empty(),
+        answer_pool_only: session.answer_pool_only,
+        known_letter_error: None,
+        suggestions_hidden: hints == HintLevel::CountOnly,
+        top_outcomes,
+        low_value_hidden,
+    }
+}
+
 // ---------- Session helpers ----------
 
 fn get_session_id(headers: &HeaderMap) -> Option<String> {
@@ -274,79 +975,68 @@ fn set_session_cookie(session_id: &str) -> (header::HeaderName, String) {
 
 // ---------- Handlers ----------
 
-async fn index(State(state): State<SharedState>, headers: HeaderMap) -> Response {
+#[derive(Deserialize)]
+struct IndexQuery {
+    /// Kept as text so a malformed value is ignored rather than failing the page.
+    limit: Option<String>,
+    /// Hint level 0-3, also kept as text.
+    hints: Option<String>,
+    /// Score floor as a percentage of the best suggestion, also kept as text.
+    min_score: Option<String>,
+}
+
+async fn index(
+    State(state): State<SharedState>,
+    headers: HeaderMap,
+    Query(query): Query<IndexQuery>,
+) -> Response {
     let session_id = get_session_id(&headers).unwrap_or_default();
 
-    let (
-        session_id_out,
-        guesses,
-        candidates_len,
-        green_disp,
-        required_disp,
-        excluded_disp,
-        ranked,
-        used_ranked,
-        loaded_at,
-        data_stale,
-    ) = {
+    let session_id_out = {
         let word_data = state.word_data.read().unwrap();
         let mut sessions = state.sessions.write().unwrap();
 
-        let sid = if sessions.contains_key(&session_id) && !session_id.is_empty() {
+        let session_id = if sessions.contains_key(&session_id) && !session_id.is_empty() {
             session_id
         } else {
             let new_id = Uuid::new_v4().to_string();
             sessions.insert(new_id.clone(), Session::new(&word_data.available_words));
             new_id
         };
+        // Kept in the session so later panel updates show the same number
+        let limit = query.limit.and_then(|l| l.parse::<usize>().ok());
+        if let (Some(limit), Some(session)) = (limit, sessions.get_mut(&session_id)) {
+            session.suggestion_limit = limit.clamp(1, MAX_SUGGESTION_LIMIT);
+        }
+        let hints = query.hints.and_then(|h| h.parse::<HintLevel>().ok());
+        if let (Some(hints), Some(session)) = (hints, sessions.get_mut(&session_id)) {
+            session.hint_level = hints;
+        }
+        let min_score = query.min_score.and_then(|m| m.parse::<f64>().ok());
+        if let (Some(min_score), Some(session)) = (min_score, sessions.get_mut(&session_id)) {
+            session.min_score = min_score.clamp(0.0, 100.0);
+        }
+        session_id
+    };
 
-        let session = sessions.get(&sid).unwrap();
-        let ranked = rank_words_owned(&session.candidates, &word_data.commonality);
-        let top: Vec<(String, f64)> = ranked.into_iter().take(15).collect();
-        let used_top = used_matches(
-            &word_data.used_words,
-            &session.state,
-            &word_data.commonality,
-        );
-
+    let panel = render_panel(&state, &session_id_out).await;
+    let template = {
+        let word_data = state.word_data.read().unwrap();
+        let sessions = state.sessions.read().unwrap();
         let stale = SystemTime::now()
             .duration_since(word_data.loaded_at)
             .unwrap_or_default()
             .as_secs()
             > 12 * 3600;
 
-        (
-            sid,
-            session.guesses.clone(),
-            session.candidates.len(),
-            session.state.green_display(),
-            session.state.required_display(),
-            session.state.excluded_display(),
-            top,
-            used_top,
-            format_timestamp(word_data.loaded_at),
-            stale,
-        )
-    };
-
-    let has_green = green_disp != "_____";
-    let has_constraints = has_green || !required_disp.is_empty() || !excluded_disp.is_empty();
-
-    let template = GameTemplate {
-        grid_rows: build_grid_rows(&guesses),
-        guess_count: guesses.len(),
-        solved: false,
-        no_matches: false,
-        suggestions: build_suggestions(&ranked),
-        used_suggestions: build_suggestions(&used_ranked),
-        candidate_count: candidates_len,
-        has_constraints,
-        has_green,
-        green_display: green_disp,
-        required_display: required_disp,
-        excluded_display: excluded_disp,
-        data_loaded_at: loaded_at,
-        data_stale,
+        let session = sessions.get(&session_id_out);
+        GameTemplate {
+            results: build_results(session, state.max_guesses),
+            panel,
+            data_loaded_at: format_timestamp(word_data.loaded_at),
+            data_stale: stale,
+            palette: session.map(|s| s.palette).unwrap_or_default(),
+        }
     };
 
     let mut response = template.into_response();
@@ -361,43 +1051,59 @@ struct GuessForm {
     feedback: String,
 }
 
+/// Lowercase form input and drop all whitespace, so " Crane " and "c r a n e" both work.
+fn sanitize_input(input: &str) -> String {
+    input
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect::<String>()
+        .to_ascii_lowercase()
+}
+
+/// The same checks the CLI applies before a guess reaches `GameState::update`.
+fn validate_guess(guess: &str, feedback: &str) -> Result<(), String> {
+    check_guess_input(guess)?;
+    check_feedback_input(feedback)
+}
+
 async fn submit_guess(
     State(state): State<SharedState>,
     headers: HeaderMap,
     Form(form): Form<GuessForm>,
 ) -> Response {
     let session_id = get_session_id(&headers).unwrap_or_default();
-    let guess = form.guess.to_ascii_lowercase();
-    let feedback = form.feedback.to_ascii_lowercase();
-
-    let (guesses, solved, no_matches) = {
-        let word_data = state.word_data.read().unwrap();
-        let mut sessions = state.sessions.write().unwrap();
-        let session = match sessions.get_mut(&session_id) {
-            Some(s) => s,
-            None => {
-                sessions.insert(session_id.clone(), Session::new(&word_data.available_words));
-                sessions.get_mut(&session_id).unwrap()
-            }
-        };
+    let guess = sanitize_input(&form.guess);
+    let feedback = sanitize_input(&form.feedback);
 
-        session.state.update(&guess, &feedback);
-        session.candidates.retain(|w| session.state.matches(w));
-        session.guesses.push((guess, feedback.clone()));
+    let word_data = state.word_data.read().unwrap();
+    let mut sessions = state.sessions.write().unwrap();
 
-        let solved = feedback == "ggggg";
-        let no_matches = session.candidates.is_empty() && !solved;
+    if let Err(message) = validate_guess(&guess, &feedback) {
+        let mut results = build_results(sessions.get(&session_id), state.max_guesses);
+        results.error = Some(message);
+        return (StatusCode::BAD_REQUEST, results).into_response();
+    }
 
-        (session.guesses.clone(), solved, no_matches)
+    // The page always creates a session, so a missing one was lost to a restart or
+    // eviction. Applying the guess to a blank game would silently drop the earlier
+    // guesses, so start fresh and ask for it again instead.
+    let Some(session) = sessions.get_mut(&session_id) else {
+        let session = Session::new(&word_data.available_words);
+        let mut results = build_results(Some(&session), state.max_guesses);
+        results.notice = Some(
+            "Your session expired, so a new game was started. Please re-enter your guesses."
+                .to_string(),
+        );
+        sessions.insert(session_id, session);
+        return results.into_response();
     };
 
-    ResultsTemplate {
-        grid_rows: build_grid_rows(&guesses),
-        guess_count: guesses.len(),
-        solved,
-        no_matches,
-    }
-    .into_response()
+    let repeat = repeated_guess(&session.guesses, &guess, &feedback);
+    session.apply_guess(guess, feedback);
+
+    let mut results = build_results(Some(session), state.max_guesses);
+    results.notice = repeat.map(|message| format!("{}.", message));
+    results.into_response()
 }
 
 async fn submit_suggestions(
@@ -406,117 +1112,792 @@ async fn submit_suggestions(
     Form(_form): Form<GuessForm>,
 ) -> Response {
     let session_id = get_session_id(&headers).unwrap_or_default();
+    render_panel(&state, &session_id).await.into_response()
+}
+
+async fn reset_game(State(state): State<SharedState>, headers: HeaderMap) -> Response {
+    let session_id = get_session_id(&headers).unwrap_or_default();
 
-    let (ranked, used_ranked, candidates_len, green_disp, required_disp, excluded_disp) = {
+    {
         let word_data = state.word_data.read().unwrap();
-        let sessions = state.sessions.read().unwrap();
-        match sessions.get(&session_id) {
-            Some(session) => {
-                let ranked = rank_words_owned(&session.candidates, &word_data.commonality);
-                let top: Vec<(String, f64)> = ranked.into_iter().take(15).collect();
-                let used_top = used_matches(
-                    &word_data.used_words,
-                    &session.state,
-                    &word_data.commonality,
-                );
+        let mut sessions = state.sessions.write().unwrap();
+        // A new game keeps the player's pool and display settings
+        let (answer_pool_only, suggestion_limit, hint_level, min_score, palette) =
+            sessions.get(&session_id).map_or(
                 (
-                    top,
-                    used_top,
-                    session.candidates.len(),
-                    session.state.green_display(),
-                    session.state.required_display(),
-                    session.state.excluded_display(),
-                )
-            }
-            None => (
-                Vec::new(),
-                Vec::new(),
-                0,
-                String::new(),
-                String::new(),
-                String::new(),
-            ),
-        }
-    };
+                    false,
+                    DEFAULT_SUGGESTION_LIMIT,
+                    HintLevel::default(),
+                    0.0,
+                    Palette::Classic,
+                ),
+                |s| {
+                    (
+                        s.answer_pool_only,
+                        s.suggestion_limit,
+                        s.hint_level,
+                        s.min_score,
+                        s.palette,
+                    )
+                },
+            );
+        let mut session = Session::new(word_data.pool(answer_pool_only));
+        session.answer_pool_only = answer_pool_only;
+        session.suggestion_limit = suggestion_limit;
+        session.hint_level = hint_level;
+        session.min_score = min_score;
+        session.palette = palette;
+        sessions.insert(session_id, session);
+    }
 
-    let has_green = green_disp != "_____" && !green_disp.is_empty();
-    let has_constraints = has_green || !required_disp.is_empty() || !excluded_disp.is_empty();
+    build_results(None, state.max_guesses).into_response()
+}
 
-    SuggestionsTemplate {
-        suggestions: build_suggestions(&ranked),
-        used_suggestions: build_suggestions(&used_ranked),
-        candidate_count: candidates_len,
-        has_constraints,
-        has_green,
-        green_display: green_disp,
-        required_display: required_disp,
-        excluded_display: excluded_disp,
-    }
-    .into_response()
+async fn reset_suggestions(State(state): State<SharedState>, headers: HeaderMap) -> Response {
+    let session_id = get_session_id(&headers).unwrap_or_default();
+    render_panel(&state, &session_id).await.into_response()
 }
 
-async fn reset_game(State(state): State<SharedState>, headers: HeaderMap) -> Response {
+#[derive(Deserialize)]
+struct AnswerPoolForm {
+    /// Present (as "on") when the checkbox is ticked.
+    answer_pool_only: Option<String>,
+}
+
+/// Switch a session between the full word list and the curated answer list, re-deriving
+/// its candidates from the chosen pool under the current constraints.
+async fn set_answer_pool(
+    State(state): State<SharedState>,
+    headers: HeaderMap,
+    Form(form): Form<AnswerPoolForm>,
+) -> Response {
     let session_id = get_session_id(&headers).unwrap_or_default();
 
     {
         let word_data = state.word_data.read().unwrap();
         let mut sessions = state.sessions.write().unwrap();
-        sessions.insert(session_id, Session::new(&word_data.available_words));
+        let session = sessions
+            .entry(session_id.clone())
+            .or_insert_with(|| Session::new(&word_data.available_words));
+
+        session.answer_pool_only = form.answer_pool_only.is_some();
+        session.candidates = word_data
+            .pool(session.answer_pool_only)
+            .iter()
+            .filter(|w| session.state.matches(w))
+            .cloned()
+            .collect();
     }
 
-    ResultsTemplate {
-        grid_rows: Vec::new(),
-        guess_count: 0,
-        solved: false,
-        no_matches: false,
-    }
-    .into_response()
+    render_panel(&state, &session_id).await.into_response()
 }
 
-async fn reset_suggestions(State(state): State<SharedState>, headers: HeaderMap) -> Response {
+#[derive(Deserialize)]
+struct KnownLetterForm {
+    /// 1-based position.
+    position: usize,
+    letter: String,
+}
+
+/// Set a letter at a known position without a guess (e.g. from a crossword clue) and
+/// re-filter the session's candidates.
+async fn set_known_letter(
+    State(state): State<SharedState>,
+    headers: HeaderMap,
+    Form(form): Form<KnownLetterForm>,
+) -> Response {
     let session_id = get_session_id(&headers).unwrap_or_default();
 
-    let (ranked, candidates_len) = {
+    let error = {
         let word_data = state.word_data.read().unwrap();
-        let sessions = state.sessions.read().unwrap();
-        match sessions.get(&session_id) {
-            Some(session) => {
-                let ranked = rank_words_owned(&session.candidates, &word_data.commonality);
-                let top: Vec<(String, f64)> = ranked.into_iter().take(15).collect();
-                (top, session.candidates.len())
+        let mut sessions = state.sessions.write().unwrap();
+        let session = sessions
+            .entry(session_id.clone())
+            .or_insert_with(|| Session::new(&word_data.available_words));
+
+        let mut letters = form.letter.trim().chars();
+        let result = match (form.position.checked_sub(1), letters.next(), letters.next()) {
+            (Some(pos), Some(letter), None) => {
+                let letter = letter.to_ascii_lowercase();
+                session.state.set_green(pos, letter).map(|()| (pos, letter))
+            }
+            _ => Err("Pick a position from 1 to 5 and a single letter".to_string()),
+        };
+        match result {
+            Ok(known) => {
+                session.known_letters.push(known);
+                session.candidates.retain(|w| session.state.matches(w));
+                // Keep the starting pool size; after a guess, its count takes the letter in
+                if !session.guesses.is_empty() {
+                    if let Some(count) = session.candidate_history.last_mut() {
+                        *count = session.candidates.len();
+                    }
+                }
+                None
             }
-            None => (Vec::new(), 0),
+            Err(e) => Some(e),
         }
     };
 
-    SuggestionsTemplate {
-        suggestions: build_suggestions(&ranked),
-        used_suggestions: Vec::new(),
-        candidate_count: candidates_len,
-        has_constraints: false,
-        has_green: false,
-        green_display: String::new(),
-        required_display: String::new(),
-        excluded_display: String::new(),
-    }
-    .into_response()
+    let mut panel = render_panel(&state, &session_id).await;
+    panel.known_letter_error = error;
+    panel.into_response()
 }
 
-async fn reload_data(State(state): State<SharedState>) -> Response {
-    println!("Reloading word data...");
+#[derive(Deserialize)]
+struct PaletteForm {
+    palette: String,
+}
 
-    let new_data = match tokio::task::spawn_blocking(load_word_data).await {
-        Ok(data) => data,
-        Err(e) => {
-            return ReloadStatusTemplate {
-                success: false,
-                message: format!("Reload failed: {}", e),
-            }
-            .into_response();
-        }
+/// Switch the session's color scheme, then reload the page to apply it.
+async fn set_palette(
+    State(state): State<SharedState>,
+    headers: HeaderMap,
+    Form(form): Form<PaletteForm>,
+) -> Response {
+    let Some(palette) = Palette::ALL.into_iter().find(|p| p.id() == form.palette) else {
+        return (StatusCode::BAD_REQUEST, "Unknown palette.").into_response();
     };
 
-    if new_data.available_words.is_empty() {
+    let session_id = get_session_id(&headers).unwrap_or_default();
+    if let Some(session) = state.sessions.write().unwrap().get_mut(&session_id) {
+        session.palette = palette;
+    }
+
+    Redirect::to("/").into_response()
+}
+
+#[derive(Serialize)]
+struct StateResponse {
+    guesses: Vec<GuessEntry>,
+    candidate_count: usize,
+    candidate_history: Vec<usize>,
+    /// Seconds from the start of the game to the latest guess.
+    play_seconds: u64,
+    green: String,
+    required: String,
+    excluded: String,
+}
+
+#[derive(Serialize)]
+struct GuessEntry {
+    guess: String,
+    feedback: String,
+    /// Seconds taken over this guess, since the previous one or the start of the game.
+    seconds: u64,
+}
+
+async fn api_state(State(state): State<SharedState>, headers: HeaderMap) -> Response {
+    let session_id = get_session_id(&headers).unwrap_or_default();
+    let sessions = state.sessions.read().unwrap();
+
+    let Some(session) = sessions.get(&session_id) else {
+        return (StatusCode::NOT_FOUND, "No active game.").into_response();
+    };
+
+    Json(state_response(session)).into_response()
+}
+
+fn state_response(session: &Session) -> StateResponse {
+    StateResponse {
+        guesses: session
+            .guesses
+            .iter()
+            .zip(&session.guessed_at)
+            .scan(session.started_at, |previous, ((guess, feedback), &at)| {
+                let seconds = at.duration_since(*previous).as_secs();
+                *previous = at;
+                Some(GuessEntry {
+                    guess: guess.clone(),
+                    feedback: feedback.clone(),
+                    seconds,
+                })
+            })
+            .collect(),
+        candidate_count: session.candidates.len(),
+        candidate_history: session.candidate_history.clone(),
+        play_seconds: session.play_time().as_secs(),
+        green: session.state.green_display(),
+        required: session.state.required_display(),
+        excluded: session.state.excluded_display(),
+    }
+}
+
+#[derive(Deserialize)]
+struct RollbackRequest {
+    /// Number of guesses to keep.
+    to: usize,
+}
+
+async fn rollback(
+    State(state): State<SharedState>,
+    headers: HeaderMap,
+    Json(request): Json<RollbackRequest>,
+) -> Response {
+    let session_id = get_session_id(&headers).unwrap_or_default();
+    let word_data = state.word_data.read().unwrap();
+    let mut sessions = state.sessions.write().unwrap();
+
+    let Some(session) = sessions.get_mut(&session_id) else {
+        return api_error(StatusCode::NOT_FOUND, "No active game.".to_string());
+    };
+
+    let pool = word_data.pool(session.answer_pool_only);
+    if let Err(message) = session.rollback(request.to, pool) {
+        return api_error(StatusCode::BAD_REQUEST, message);
+    }
+
+    Json(state_response(session)).into_response()
+}
+
+fn api_error(status: StatusCode, message: String) -> Response {
+    (status, Json(serde_json::json!({ "error": message }))).into_response()
+}
+
+#[derive(Deserialize)]
+struct AdversarialRequest {
+    guess: String,
+}
+
+#[derive(Serialize)]
+struct AdversarialResponse {
+    feedback: String,
+    candidate_count: usize,
+    solved: bool,
+}
+
+/// Absurdle-style play: there is no fixed answer, and each guess gets the feedback that
+/// keeps the largest group of candidates, applied to the session like a normal guess.
+async fn api_adversarial(
+    State(state): State<SharedState>,
+    headers: HeaderMap,
+    Json(req): Json<AdversarialRequest>,
+) -> Response {
+    let session_id = get_session_id(&headers).unwrap_or_default();
+    let guess = req.guess.trim().to_ascii_lowercase();
+    if !is_valid_word(&guess) {
+        return api_error(
+            StatusCode::BAD_REQUEST,
+            format!("Invalid guess: {}", req.guess),
+        );
+    }
+
+    let word_data = state.word_data.read().unwrap();
+    let mut sessions = state.sessions.write().unwrap();
+    let session = sessions
+        .entry(session_id)
+        .or_insert_with(|| Session::new(&word_data.available_words));
+
+    let refs: Vec<&String> = session.candidates.iter().collect();
+    let Some(feedback) = adversarial_feedback(&guess, &refs) else {
+        return api_error(StatusCode::CONFLICT, "No candidates remain.".to_string());
+    };
+
+    session.apply_guess(guess, feedback.clone());
+
+    Json(AdversarialResponse {
+        solved: feedback == "ggggg",
+        feedback,
+        candidate_count: session.candidates.len(),
+    })
+    .into_response()
+}
+
+/// The session's remaining candidates, each with the letters that set it apart.
+async fn api_candidates(State(state): State<SharedState>, headers: HeaderMap) -> Response {
+    let session_id = get_session_id(&headers).unwrap_or_default();
+    let sessions = state.sessions.read().unwrap();
+
+    let Some(session) = sessions.get(&session_id) else {
+        return (StatusCode::NOT_FOUND, "No active game.").into_response();
+    };
+
+    let refs: Vec<&String> = session.candidates.iter().collect();
+    Json(candidate_reasons(&refs)).into_response()
+}
+
+#[derive(Deserialize)]
+struct PatternsQuery {
+    guess: String,
+}
+
+#[derive(Serialize)]
+struct PatternEntry {
+    feedback: String,
+    count: usize,
+}
+
+/// The feedback patterns a guess could still get against the session's candidates.
+async fn api_patterns(
+    State(state): State<SharedState>,
+    headers: HeaderMap,
+    Query(query): Query<PatternsQuery>,
+) -> Response {
+    let session_id = get_session_id(&headers).unwrap_or_default();
+    let guess = query.guess.trim().to_ascii_lowercase();
+    if !is_valid_word(&guess) {
+        return api_error(StatusCode::BAD_REQUEST, format!("Invalid word: {}", guess));
+    }
+
+    let word_data = state.word_data.read().unwrap();
+    let sessions = state.sessions.read().unwrap();
+    let (game, candidates): (GameState, Vec<&String>) = match sessions.get(&session_id) {
+        Some(session) => (session.state.clone(), session.candidates.iter().collect()),
+        None => (GameState::new(), word_data.available_words.iter().collect()),
+    };
+
+    let patterns: Vec<PatternEntry> = possible_feedbacks(&game, &guess, &candidates)
+        .into_iter()
+        .map(|(feedback, count)| PatternEntry { feedback, count })
+        .collect();
+    Json(serde_json::json!({
+        "guess": guess,
+        "candidate_count": candidates.len(),
+        "patterns": patterns,
+    }))
+    .into_response()
+}
+
+/// The session's candidates grouped by where they put each unplaced yellow letter.
+async fn api_yellows(State(state): State<SharedState>, headers: HeaderMap) -> Response {
+    let session_id = get_session_id(&headers).unwrap_or_default();
+    let sessions = state.sessions.read().unwrap();
+
+    let Some(session) = sessions.get(&session_id) else {
+        return (StatusCode::NOT_FOUND, "No active game.").into_response();
+    };
+
+    let refs: Vec<&String> = session.candidates.iter().collect();
+    Json(yellow_placements(&refs, &session.state)).into_response()
+}
+
+#[derive(Deserialize)]
+struct CompareRequest {
+    first: String,
+    second: String,
+}
+
+/// Compare two guesses against the session's current candidates.
+async fn api_compare(
+    State(state): State<SharedState>,
+    headers: HeaderMap,
+    Json(req): Json<CompareRequest>,
+) -> Response {
+    let session_id = get_session_id(&headers).unwrap_or_default();
+    let words = [req.first, req.second].map(|w| w.trim().to_ascii_lowercase());
+    if let Some(bad) = words.iter().find(|w| !is_valid_word(w)) {
+        return api_error(StatusCode::BAD_REQUEST, format!("Invalid word: {}", bad));
+    }
+
+    let word_data = state.word_data.read().unwrap();
+    let sessions = state.sessions.read().unwrap();
+    let candidates: Vec<&String> = match sessions.get(&session_id) {
+        Some(session) => session.candidates.iter().collect(),
+        None => word_data.available_words.iter().collect(),
+    };
+
+    Json(words.map(|w| compare_guess(&w, &candidates, &word_data.commonality))).into_response()
+}
+
+#[derive(Deserialize)]
+struct ValidateRequest {
+    guess: String,
+    feedback: String,
+    answer: String,
+}
+
+#[derive(Serialize)]
+struct ValidateResponse {
+    correct: bool,
+    /// The right pattern when `feedback` isn't it.
+    expected: Option<String>,
+}
+
+/// Stateless check of whether `feedback` is what `guess` scores against `answer`.
+async fn api_validate(Json(req): Json<ValidateRequest>) -> Response {
+    let guess = req.guess.trim().to_ascii_lowercase();
+    let answer = req.answer.trim().to_ascii_lowercase();
+    let feedback = req.feedback.trim().to_ascii_lowercase();
+    if let Some(bad) = [&guess, &answer].into_iter().find(|w| !is_valid_word(w)) {
+        return api_error(StatusCode::BAD_REQUEST, format!("Invalid word: {}", bad));
+    }
+    if feedback.len() != 5 || !feedback.chars().all(|c| matches!(c, 'g' | 'y' | 'x')) {
+        return api_error(
+            StatusCode::BAD_REQUEST,
+            "feedback must be 5 characters, each g, y or x".to_string(),
+        );
+    }
+
+    let actual = feedback_for(&guess, &answer);
+    Json(ValidateResponse {
+        correct: actual == feedback,
+        expected: (actual != feedback).then_some(actual),
+    })
+    .into_response()
+}
+
+/// Most answers a single `/api/simulate` request may evaluate.
+const SIMULATE_MAX_ANSWERS: usize = 100;
+
+#[derive(Deserialize)]
+struct SimulateRequest {
+    opener: String,
+    answers: Vec<String>,
+}
+
+#[derive(Serialize)]
+struct SimulateResult {
+    answer: String,
+    feedback: String,
+    /// Candidates still matching after the opener's feedback.
+    candidates_left: usize,
+}
+
+/// Stateless batch: the feedback `opener` gets against each answer and how many of the
+/// full candidate list survive it.
+async fn api_simulate(
+    State(state): State<SharedState>,
+    Json(req): Json<SimulateRequest>,
+) -> Response {
+    let opener = req.opener.trim().to_ascii_lowercase();
+    if !is_valid_word(&opener) {
+        return api_error(
+            StatusCode::BAD_REQUEST,
+            format!("Invalid opener: {}", req.opener),
+        );
+    }
+    if req.answers.len() > SIMULATE_MAX_ANSWERS {
+        return api_error(
+            StatusCode::BAD_REQUEST,
+            format!("At most {} answers per request", SIMULATE_MAX_ANSWERS),
+        );
+    }
+
+    let word_data = state.word_data.read().unwrap();
+    let mut results = Vec::with_capacity(req.answers.len());
+    for answer in &req.answers {
+        let answer = answer.trim().to_ascii_lowercase();
+        if !is_valid_word(&answer) {
+            return api_error(
+                StatusCode::BAD_REQUEST,
+                format!("Invalid answer: {}", answer),
+            );
+        }
+        let feedback = feedback_for(&opener, &answer);
+        let mut constraints = GameState::new();
+        constraints.update(&opener, &feedback);
+        let candidates_left = word_data
+            .available_words
+            .iter()
+            .filter(|w| constraints.matches(w))
+            .count();
+        results.push(SimulateResult {
+            answer,
+            feedback,
+            candidates_left,
+        });
+    }
+
+    Json(results).into_response()
+}
+
+#[derive(Deserialize)]
+struct RankRequest {
+    /// Five entries, each a letter or null.
+    #[serde(default)]
+    greens: Option<Vec<Option<char>>>,
+    /// Five strings of letters ruled out at each position (each also required).
+    #[serde(default)]
+    yellows: Option<Vec<String>>,
+    #[serde(default)]
+    required: String,
+    #[serde(default)]
+    excluded: String,
+    limit: Option<usize>,
+}
+
+#[derive(Serialize)]
+struct RankResponse {
+    candidate_count: usize,
+    suggestions: Vec<RankedWord>,
+}
+
+#[derive(Serialize)]
+struct RankedWord {
+    word: String,
+    score: f64,
+}
+
+/// Build a `GameState` directly from a constraint specification.
+fn constraints_from_request(req: &RankRequest) -> Result<GameState, String> {
+    let mut state = GameState::new();
+
+    if let Some(greens) = &req.greens {
+        if greens.len() != 5 {
+            return Err("greens must have exactly 5 entries".to_string());
+        }
+        for (pos, letter) in greens.iter().enumerate() {
+            if let Some(letter) = letter {
+                state.set_green(pos, letter.to_ascii_lowercase())?;
+            }
+        }
+    }
+    for letter in req.required.chars() {
+        state.require(letter.to_ascii_lowercase())?;
+    }
+    if let Some(yellows) = &req.yellows {
+        if yellows.len() != 5 {
+            return Err("yellows must have exactly 5 entries".to_string());
+        }
+        for (pos, letters) in yellows.iter().enumerate() {
+            for letter in letters.chars() {
+                state.add_yellow(pos, letter.to_ascii_lowercase())?;
+            }
+        }
+    }
+    for letter in req.excluded.chars() {
+        state.exclude(letter.to_ascii_lowercase())?;
+    }
+
+    Ok(state)
+}
+
+/// Largest page `/api/suggestions` returns.
+const SUGGESTIONS_PAGE_MAX: usize = 100;
+
+#[derive(Deserialize)]
+struct PageQuery {
+    offset: Option<usize>,
+    limit: Option<usize>,
+}
+
+#[derive(Serialize)]
+struct SuggestionsPage {
+    total: usize,
+    offset: usize,
+    limit: usize,
+    suggestions: Vec<RankedWord>,
+}
+
+/// One page of the session's full ranking (favorites pinned first). The ranking is
+/// computed once and cached in the session until its guesses, pool or word data change.
+async fn api_suggestions(
+    State(state): State<SharedState>,
+    headers: HeaderMap,
+    Query(page): Query<PageQuery>,
+) -> Response {
+    let offset = page.offset.unwrap_or(0);
+    let limit = page.limit.unwrap_or(15);
+    if !(1..=SUGGESTIONS_PAGE_MAX).contains(&limit) {
+        return api_error(
+            StatusCode::BAD_REQUEST,
+            format!("limit must be between 1 and {SUGGESTIONS_PAGE_MAX}"),
+        );
+    }
+
+    let session_id = get_session_id(&headers).unwrap_or_default();
+    let (key, cached, inputs, commonality, options) = {
+        let word_data = state.word_data.read().unwrap();
+        let sessions = state.sessions.read().unwrap();
+        let Some(session) = sessions.get(&session_id) else {
+            return api_error(StatusCode::NOT_FOUND, "No active game.".to_string());
+        };
+        let key = RankKey {
+            guesses: session.guesses.clone(),
+            known_letters: session.known_letters.clone(),
+            answer_pool_only: session.answer_pool_only,
+            loaded_at: word_data.loaded_at,
+        };
+        let cached = session
+            .ranked
+            .as_ref()
+            .filter(|cached| cached.key == key)
+            .map(|cached| Arc::clone(&cached.ranked));
+        (
+            key,
+            cached,
+            (session.candidates.clone(), session.state.clone()),
+            Arc::clone(&word_data.commonality),
+            rank_options_for(&word_data, &state),
+        )
+    };
+
+    let ranked = match cached {
+        Some(ranked) => ranked,
+        None => {
+            let task = tokio::task::spawn_blocking(move || {
+                let (candidates, game) = inputs;
+                let mut ranked = rank_words_owned_with(&candidates, &commonality, &game, &options);
+                ranked.retain(|(w, _)| !game.guessed.contains(w));
+                ranked
+            });
+            let Ok(mut ranked) = task.await else {
+                return api_error(
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "Ranking failed.".to_string(),
+                );
+            };
+            pin_favorites(&mut ranked, &state.favorites);
+            let ranked = Arc::new(ranked);
+
+            let mut sessions = state.sessions.write().unwrap();
+            if let Some(session) = sessions.get_mut(&session_id) {
+                session.ranked = Some(CachedRanking {
+                    key,
+                    ranked: Arc::clone(&ranked),
+                });
+            }
+            ranked
+        }
+    };
+
+    Json(SuggestionsPage {
+        total: ranked.len(),
+        offset,
+        limit,
+        suggestions: ranked
+            .iter()
+            .skip(offset)
+            .take(limit)
+            .map(|(word, score)| RankedWord {
+                word: word.clone(),
+                score: *score,
+            })
+            .collect(),
+    })
+    .into_response()
+}
+
+/// Version and data provenance: the sources the loaded word data came from and when.
+async fn about(State(state): State<SharedState>) -> Response {
+    let word_data = state.word_data.read().unwrap();
+    Json(serde_json::json!({
+        "version": env!("CARGO_PKG_VERSION"),
+        "sources": word_data.sources,
+        "answer_list": std::env::var("WORDLE_ANSWER_LIST").ok(),
+        "loaded_at": format_timestamp(word_data.loaded_at),
+    }))
+    .into_response()
+}
+
+#[derive(Deserialize)]
+struct NextQuery {
+    opener: String,
+    feedback: String,
+}
+
+/// Look up the precomputed best second guess after `opener` got `feedback`.
+async fn api_next(State(state): State<SharedState>, Query(query): Query<NextQuery>) -> Response {
+    let opener = query.opener.trim().to_ascii_lowercase();
+    let feedback = query.feedback.trim().to_ascii_lowercase();
+    if feedback.len() != 5 || !feedback.chars().all(|c| matches!(c, 'g' | 'y' | 'x')) {
+        return api_error(
+            StatusCode::BAD_REQUEST,
+            "feedback must be 5 characters, each g, y or x".to_string(),
+        );
+    }
+
+    let word_data = state.word_data.read().unwrap();
+    let Some(tree) = word_data.opener_trees.get(&opener) else {
+        return api_error(
+            StatusCode::NOT_FOUND,
+            format!(
+                "No precomputed tree for '{}'. Available openers: {}",
+                opener,
+                word_data.opener_trees.keys().sorted().join(", ")
+            ),
+        );
+    };
+    if feedback == "ggggg" {
+        return api_error(
+            StatusCode::BAD_REQUEST,
+            format!("'{}' was the answer: nothing to guess next", opener),
+        );
+    }
+    let Some(next) = tree.get(&feedback) else {
+        return api_error(
+            StatusCode::NOT_FOUND,
+            format!("No remaining candidate gives {} for {}", feedback, opener),
+        );
+    };
+
+    Json(serde_json::json!({ "opener": opener, "feedback": feedback, "next": next }))
+        .into_response()
+}
+
+/// Rank candidates for a hypothetical constraint set given directly as JSON.
+async fn api_rank(State(state): State<SharedState>, Json(req): Json<RankRequest>) -> Response {
+    let constraints = match constraints_from_request(&req) {
+        Ok(c) => c,
+        Err(e) => return api_error(StatusCode::BAD_REQUEST, e),
+    };
+    let limit = req.limit.unwrap_or(15).clamp(1, 100);
+
+    let word_data = state.word_data.read().unwrap();
+    let candidates: Vec<&String> = word_data
+        .available_words
+        .iter()
+        .filter(|w| constraints.matches(w))
+        .collect();
+    let ranked = rank_words_with(
+        &candidates,
+        &word_data.commonality,
+        &constraints,
+        &state.rank_options,
+    );
+
+    Json(RankResponse {
+        candidate_count: candidates.len(),
+        suggestions: ranked
+            .into_iter()
+            .take(limit)
+            .map(|(word, score)| RankedWord {
+                word: word.clone(),
+                score,
+            })
+            .collect(),
+    })
+    .into_response()
+}
+
+async fn report(State(state): State<SharedState>, headers: HeaderMap) -> Response {
+    let session_id = get_session_id(&headers).unwrap_or_default();
+
+    let body = {
+        let word_data = state.word_data.read().unwrap();
+        let sessions = state.sessions.read().unwrap();
+        match sessions.get(&session_id) {
+            Some(session) => build_report(session, &word_data, &state),
+            None => "No active game.\n".to_string(),
+        }
+    };
+
+    ([(header::CONTENT_TYPE, "text/plain; charset=utf-8")], body).into_response()
+}
+
+async fn reload_data(State(state): State<SharedState>) -> Response {
+    let Ok(_permit) = state.reload_permit.try_acquire() else {
+        return ReloadStatusTemplate {
+            success: false,
+            message: "Reload already in progress.".to_string(),
+        }
+        .into_response();
+    };
+
+    println!("Reloading word data...");
+
+    let new_data = match tokio::task::spawn_blocking(load_word_data).await {
+        Ok(data) => data,
+        Err(e) => {
+            return ReloadStatusTemplate {
+                success: false,
+                message: format!("Reload failed: {}", e),
+            }
+            .into_response();
+        }
+    };
+
+    if new_data.available_words.is_empty() {
         return ReloadStatusTemplate {
             success: false,
             message: "Reload failed: no words loaded.".to_string(),
@@ -525,6 +1906,11 @@ async fn reload_data(State(state): State<SharedState>) -> Response {
     }
 
     let count = new_data.available_words.len();
+    let unchanged = if new_data.word_list_status == FetchStatus::Unchanged {
+        " Word list unchanged (304)."
+    } else {
+        ""
+    };
 
     {
         let mut word_data = state.word_data.write().unwrap();
@@ -539,13 +1925,95 @@ async fn reload_data(State(state): State<SharedState>) -> Response {
 
     ReloadStatusTemplate {
         success: true,
-        message: format!("Reloaded. {} candidates available.", count),
+        message: format!("Reloaded. {} candidates available.{}", count, unchanged),
     }
     .into_response()
 }
 
+// ---------- Admin ----------
+
+/// Whether the request carries `Authorization: Bearer <token>` matching the admin token.
+/// Compares in constant time so the token can't be guessed byte by byte from timings.
+fn is_admin(app: &AppState, headers: &HeaderMap) -> bool {
+    let Some(expected) = &app.admin_token else {
+        return false;
+    };
+    let Some(given) = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "))
+    else {
+        return false;
+    };
+    given.len() == expected.len()
+        && given
+            .bytes()
+            .zip(expected.bytes())
+            .fold(0, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
+
+/// Drop every session without touching the word data, e.g. to free memory.
+async fn clear_sessions(State(state): State<SharedState>, headers: HeaderMap) -> Response {
+    if !is_admin(&state, &headers) {
+        return api_error(StatusCode::UNAUTHORIZED, "Invalid admin token.".to_string());
+    }
+
+    let cleared = {
+        let mut sessions = state.sessions.write().unwrap();
+        let count = sessions.len();
+        sessions.clear();
+        count
+    };
+    println!("Cleared {} sessions.", cleared);
+
+    Json(serde_json::json!({ "cleared": cleared })).into_response()
+}
+
 // ---------- Main ----------
 
+/// A non-negative number from the environment, warning about (and ignoring) bad values.
+fn env_weight(name: &str) -> Option<f64> {
+    let value = std::env::var(name).ok()?;
+    match value.parse::<f64>() {
+        Ok(w) if w >= 0.0 => Some(w),
+        _ => {
+            eprintln!("Warning: ignoring invalid {}: {}", name, value);
+            None
+        }
+    }
+}
+
+/// Every page and API route, served from `state`.
+fn router(state: SharedState) -> Router {
+    Router::new()
+        .route("/", get(index))
+        .route("/guess", post(submit_guess))
+        .route("/suggestions", post(submit_suggestions))
+        .route("/reset", post(reset_game))
+        .route("/reset-suggestions", post(reset_suggestions))
+        .route("/answer-pool", post(set_answer_pool))
+        .route("/palette", post(set_palette))
+        .route("/known-letter", post(set_known_letter))
+        .route("/rollback", post(rollback))
+        .route("/report.txt", get(report))
+        .route("/about", get(about))
+        .route("/api/state", get(api_state))
+        .route("/api/candidates", get(api_candidates))
+        .route("/api/yellows", get(api_yellows))
+        .route("/api/suggestions", post(api_suggestions))
+        .route("/api/next", get(api_next))
+        .route("/api/rank", post(api_rank))
+        .route("/api/adversarial", post(api_adversarial))
+        .route("/api/simulate", post(api_simulate))
+        .route("/api/compare", post(api_compare))
+        .route("/api/validate", post(api_validate))
+        .route("/api/patterns", get(api_patterns))
+        .route("/reload", post(reload_data))
+        .route("/admin/clear-sessions", post(clear_sessions))
+        .with_state(state)
+}
+
 #[tokio::main]
 async fn main() {
     println!("Wordle Solver - Loading word lists...");
@@ -554,22 +2022,139 @@ async fn main() {
         .await
         .expect("Failed to load word lists");
 
+    let mut rank_options = RankOptions::default();
+    if let Some(w) = env_weight("WORDLE_YELLOW_BONUS") {
+        rank_options.yellow_bonus_weight = w;
+    }
+    rank_options.rare_bigram_threshold = env_weight("WORDLE_RARE_BIGRAMS");
+    if let Some(t) = env_weight("WORDLE_COMMON_THRESHOLD") {
+        rank_options.common_threshold = t;
+    }
+    let max_guesses = match std::env::var("WORDLE_MAX_GUESSES") {
+        Ok(value) => match value.parse::<usize>() {
+            Ok(n) if n >= 1 => n,
+            _ => {
+                eprintln!("Warning: ignoring invalid WORDLE_MAX_GUESSES: {}", value);
+                MAX_GUESSES
+            }
+        },
+        Err(_) => MAX_GUESSES,
+    };
+    if let Ok(name) = std::env::var("WORDLE_STRATEGY") {
+        match name.parse::<Strategy>() {
+            Ok(strategy) => rank_options.strategy = strategy,
+            Err(e) => eprintln!("Warning: ignoring WORDLE_STRATEGY: {}", e),
+        }
+    }
+    if rank_options.strategy == Strategy::Common && word_data.commonality.is_empty() {
+        eprintln!("Warning: no word frequency data loaded, so common mode ranks by letter score.");
+    }
+
+    let favorites = match std::env::var("WORDLE_FAVORITES") {
+        Ok(path) => match std::fs::read_to_string(&path) {
+            Ok(content) => parse_word_list(&content).0,
+            Err(e) => {
+                eprintln!("Warning: couldn't read favorites file {}: {}", path, e);
+                HashSet::new()
+            }
+        },
+        Err(_) => HashSet::new(),
+    };
+
     let state = Arc::new(AppState {
         word_data: RwLock::new(word_data),
         sessions: RwLock::new(HashMap::new()),
+        rank_options,
+        favorites,
+        past_pattern_weight: env_weight("WORDLE_PAST_PATTERN_BIAS"),
+        answer_position_weight: env_weight("WORDLE_ANSWER_POSITIONS"),
+        normalized_scores: std::env::var("WORDLE_NORMALIZED_SCORES").is_ok_and(|v| v == "1"),
+        max_guesses,
+        admin_token: std::env::var("WORDLE_ADMIN_TOKEN")
+            .ok()
+            .filter(|t| !t.is_empty()),
+        reload_permit: Semaphore::new(1),
     });
 
-    let app = Router::new()
-        .route("/", get(index))
-        .route("/guess", post(submit_guess))
-        .route("/suggestions", post(submit_suggestions))
-        .route("/reset", post(reset_game))
-        .route("/reset-suggestions", post(reset_suggestions))
-        .route("/reload", post(reload_data))
-        .with_state(state);
+    let app = router(state);
 
     println!("Server running at http://localhost:3000");
 
     let listener = tokio::net::TcpListener::bind("0.0.0.0:3000").await.unwrap();
     axum::serve(listener, app).await.unwrap();
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, http::Request};
+    use tower::ServiceExt;
+
+    /// Server state over a fixed word list, with nothing fetched.
+    fn test_state(words: &[&str]) -> SharedState {
+        Arc::new(AppState {
+            word_data: RwLock::new(WordData {
+                available_words: words.iter().map(|w| w.to_string()).collect(),
+                answer_words: Vec::new(),
+                recommended_opener: None,
+                opener_trees: HashMap::new(),
+                word_list_status: FetchStatus::Downloaded,
+                used_words: Vec::new(),
+                commonality: Arc::new(HashMap::new()),
+                sources: DataSources::configured(),
+                loaded_at: SystemTime::now(),
+            }),
+            sessions: RwLock::new(HashMap::new()),
+            rank_options: RankOptions::default(),
+            favorites: HashSet::new(),
+            past_pattern_weight: None,
+            answer_position_weight: None,
+            normalized_scores: false,
+            max_guesses: MAX_GUESSES,
+            admin_token: None,
+            reload_permit: Semaphore::new(1),
+        })
+    }
+
+    #[tokio::test]
+    async fn malformed_guess_is_rejected_without_changing_the_session() {
+        let state = test_state(&["crane", "crate", "trace", "slate"]);
+        {
+            let mut session = Session::new(&state.word_data.read().unwrap().available_words);
+            session.apply_guess("slate".to_string(), "xxggg".to_string());
+            state
+                .sessions
+                .write()
+                .unwrap()
+                .insert("test".to_string(), session);
+        }
+
+        let request = Request::post("/guess")
+            .header(header::COOKIE, "session=test")
+            .header(header::CONTENT_TYPE, "application/x-www-form-urlencoded")
+            .body(Body::from("guess=cr&feedback=ggggg"))
+            .unwrap();
+        let response = router(state.clone()).oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let html = String::from_utf8_lossy(&body);
+        assert!(
+            html.contains(
+                r#"<div class="message warning">Guess must be exactly 5 letters (got 2).</div>"#
+            ),
+            "{}",
+            html
+        );
+
+        let sessions = state.sessions.read().unwrap();
+        let session = &sessions["test"];
+        assert_eq!(
+            session.guesses,
+            vec![("slate".to_string(), "xxggg".to_string())]
+        );
+        assert_eq!(session.candidates, vec!["crate".to_string()]);
+        assert_eq!(session.candidate_history, vec![4, 1]);
+    }
+}
@@ -1,26 +1,38 @@
 use itertools::Itertools;
+use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 
 // ---------- Word fetching ----------
+// Network access is behind the `fetch` feature; parsing works without it.
 
+#[cfg(feature = "fetch")]
 pub fn used_words() -> HashSet<String> {
-    let response =
-        match reqwest::blocking::get("https://www.rockpapershotgun.com/wordle-past-answers") {
-            Ok(r) => r,
-            Err(e) => {
-                eprintln!(
-                    "Warning: couldn't fetch past answers: {}. Proceeding with full word list.",
-                    e
-                );
-                return HashSet::new();
-            }
-        };
+    used_words_dated()
+        .into_iter()
+        .map(|answer| answer.word)
+        .collect()
+}
 
-    let html_content = match response.text() {
-        Ok(t) => t,
+/// A past answer, with the day it was used (days since 1970-01-01) when the source
+/// gives one.
+#[derive(Clone, Debug)]
+pub struct PastAnswer {
+    pub word: String,
+    pub day: Option<i64>,
+}
+
+/// Past answers with any dates the source lists next to them.
+#[cfg(feature = "fetch")]
+pub fn used_words_dated() -> Vec<PastAnswer> {
+    let html_content = match fetch_cached(PAST_ANSWERS_URL) {
+        Ok((t, _)) => t,
         Err(e) => {
-            eprintln!("Warning: couldn't read past answers response: {}.", e);
-            return HashSet::new();
+            eprintln!(
+                "Warning: couldn't fetch past answers: {}. Proceeding with full word list.",
+                e
+            );
+            return Vec::new();
         }
     };
 
@@ -31,43 +43,499 @@ pub fn used_words() -> HashSet<String> {
 
     let Some(div) = document.select(&div_selector).next() else {
         eprintln!("Warning: page structure changed, couldn't find article body.");
-        return HashSet::new();
+        return Vec::new();
     };
     let Some(ul) = div.select(&ul_selector).next() else {
         eprintln!("Warning: page structure changed, couldn't find word list.");
-        return HashSet::new();
+        return Vec::new();
     };
 
-    let mut words = HashSet::new();
+    let mut answers: Vec<PastAnswer> = Vec::new();
     for li in ul.select(&li_selector) {
         let text = li.text().collect::<Vec<_>>();
         if let Some(first) = text.first() {
             let word = first.trim().to_ascii_lowercase();
-            if word.len() == 5 && word.chars().all(|c| c.is_ascii_lowercase()) {
-                words.insert(word);
+            if is_valid_word(&word) && !answers.iter().any(|a| a.word == word) {
+                answers.push(PastAnswer {
+                    word,
+                    day: parse_answer_date(&text.concat()),
+                });
             }
         }
     }
-    words
+    answers
 }
 
-pub fn all_words() -> Result<HashSet<String>, String> {
-    let response = reqwest::blocking::get(
-        "https://raw.githubusercontent.com/tabatkins/wordle-list/refs/heads/main/words",
+/// Past answers used within the last `days` days before `today` (days since 1970-01-01),
+/// plus any undated ones to stay on the safe side. `None` when no answer has a date, so
+/// the caller can fall back to excluding them all.
+pub fn recent_answers(answers: &[PastAnswer], days: u64, today: i64) -> Option<HashSet<String>> {
+    if answers.iter().all(|a| a.day.is_none()) {
+        return None;
+    }
+    let cutoff = today - days as i64;
+    Some(
+        answers
+            .iter()
+            .filter(|a| a.day.is_none_or(|day| day >= cutoff))
+            .map(|a| a.word.clone())
+            .collect(),
+    )
+}
+
+/// Find a date like `2024-03-15`, `March 15, 2024` or `15 Mar 2024` in `text`, as days
+/// since 1970-01-01.
+pub fn parse_answer_date(text: &str) -> Option<i64> {
+    for token in text.split(|c: char| !c.is_ascii_alphanumeric() && c != '-') {
+        let parts: Vec<&str> = token.split('-').collect();
+        if let [y, m, d] = parts[..] {
+            if y.len() == 4 {
+                if let (Ok(y), Ok(m), Ok(d)) = (y.parse(), m.parse(), d.parse()) {
+                    if (1..=12).contains(&m) && (1..=31).contains(&d) {
+                        return Some(days_from_civil(y, m, d));
+                    }
+                }
+            }
+        }
+    }
+
+    const MONTHS: [&str; 12] = [
+        "jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec",
+    ];
+    let tokens: Vec<String> = text
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|t| !t.is_empty())
+        .map(|t| t.to_ascii_lowercase())
+        .collect();
+    let number = |i: usize, len: std::ops::RangeInclusive<usize>| {
+        tokens
+            .get(i)
+            .filter(|t| len.contains(&t.len()))
+            .and_then(|t| t.parse::<i64>().ok())
+    };
+    for (i, token) in tokens.iter().enumerate() {
+        let Some(month) = MONTHS
+            .iter()
+            .position(|m| token.len() >= 3 && m.starts_with(&token[..3]))
+            .filter(|_| token.chars().all(|c| c.is_ascii_alphabetic()))
+        else {
+            continue;
+        };
+        // "March 15 2024" or "15 March 2024"
+        let date = match (number(i + 1, 1..=2), number(i + 2, 4..=4)) {
+            (Some(d), Some(y)) => Some((y, d)),
+            _ => match (
+                i.checked_sub(1).and_then(|j| number(j, 1..=2)),
+                number(i + 1, 4..=4),
+            ) {
+                (Some(d), Some(y)) => Some((y, d)),
+                _ => None,
+            },
+        };
+        if let Some((y, d)) = date.filter(|(_, d)| (1..=31).contains(d)) {
+            return Some(days_from_civil(y, month as i64 + 1, d));
+        }
+    }
+    None
+}
+
+/// A timestamp as `YYYY-MM-DD HH:MM UTC`.
+pub fn format_timestamp(t: std::time::SystemTime) -> String {
+    let secs = t
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    // Convert to date/time components (UTC)
+    let days = secs / 86400;
+    let time_secs = secs % 86400;
+    let hours = time_secs / 3600;
+    let minutes = (time_secs % 3600) / 60;
+
+    // Days since 1970-01-01 to Y-M-D
+    let mut y = 1970i64;
+    let mut remaining = days as i64;
+    loop {
+        let days_in_year = if y % 4 == 0 && (y % 100 != 0 || y % 400 == 0) {
+            366
+        } else {
+            365
+        };
+        if remaining < days_in_year {
+            break;
+        }
+        remaining -= days_in_year;
+        y += 1;
+    }
+    let leap = y % 4 == 0 && (y % 100 != 0 || y % 400 == 0);
+    let month_days = [
+        31,
+        if leap { 29 } else { 28 },
+        31,
+        30,
+        31,
+        30,
+        31,
+        31,
+        30,
+        31,
+        30,
+        31,
+    ];
+    let mut m = 0;
+    for md in &month_days {
+        if remaining < *md {
+            break;
+        }
+        remaining -= md;
+        m += 1;
+    }
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02} UTC",
+        y,
+        m + 1,
+        remaining + 1,
+        hours,
+        minutes
     )
-    .map_err(|e| format!("Failed to fetch word list: {}", e))?;
+}
+
+/// Days since 1970-01-01 for a proleptic Gregorian date.
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = y.div_euclid(400);
+    let year_of_era = y - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146097 + day_of_era - 719468
+}
+
+/// Today as days since 1970-01-01 (UTC).
+pub fn today() -> i64 {
+    let secs = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs());
+    (secs / 86400) as i64
+}
+
+#[cfg(feature = "fetch")]
+pub fn all_words() -> Result<HashSet<String>, String> {
+    fetch_word_list().map(|(words, _)| words)
+}
+
+#[cfg(feature = "fetch")]
+const PAST_ANSWERS_URL: &str = "https://www.rockpapershotgun.com/wordle-past-answers";
+
+/// Where the word data comes from, for reporting which data produced a result.
+#[cfg(feature = "fetch")]
+#[derive(Clone, Debug, Serialize)]
+pub struct DataSources {
+    pub word_list: String,
+    pub past_answers: String,
+    pub frequency: Vec<String>,
+}
+
+#[cfg(feature = "fetch")]
+impl DataSources {
+    /// The configured sources, honoring `WORDLE_WORD_LIST` and `WORDLE_FREQUENCY_SOURCES`.
+    pub fn configured() -> Self {
+        Self {
+            word_list: std::env::var("WORDLE_WORD_LIST")
+                .unwrap_or_else(|_| DEFAULT_WORD_LIST_URL.to_string()),
+            past_answers: PAST_ANSWERS_URL.to_string(),
+            frequency: frequency_sources(),
+        }
+    }
+}
+
+/// When `url` was last downloaded into the cache, if it has been.
+#[cfg(feature = "fetch")]
+pub fn cached_at(url: &str) -> Option<std::time::SystemTime> {
+    std::fs::metadata(cache_paths(url).0).ok()?.modified().ok()
+}
+
+#[cfg(feature = "fetch")]
+const DEFAULT_WORD_LIST_URL: &str =
+    "https://raw.githubusercontent.com/tabatkins/wordle-list/refs/heads/main/words";
+
+/// Fetch the valid word list, also reporting whether it changed since the cached copy.
+/// `WORDLE_WORD_LIST` (a URL or file, e.g. the official NYT list) replaces the default
+/// tabatkins list, which is still used if it can't be loaded.
+#[cfg(feature = "fetch")]
+pub fn fetch_word_list() -> Result<(HashSet<String>, FetchStatus), String> {
+    let custom = std::env::var("WORDLE_WORD_LIST").ok().and_then(|location| {
+        read_word_source(&location)
+            .inspect_err(|e| {
+                eprintln!(
+                    "Warning: couldn't load word list {}: {}. Using the default list.",
+                    location, e
+                )
+            })
+            .ok()
+    });
+    let (content, status) = match custom {
+        Some(loaded) => loaded,
+        None => fetch_cached(DEFAULT_WORD_LIST_URL)
+            .map_err(|e| format!("Failed to fetch word list: {}", e))?,
+    };
+
+    let (words, rejected) = parse_word_source(&content);
+    if rejected > 0 {
+        eprintln!(
+            "Warning: skipped {} malformed word list entries (not 5 lowercase letters).",
+            rejected
+        );
+    }
+
+    Ok((words, status))
+}
 
-    let content = response
-        .text()
-        .map_err(|e| format!("Failed to read word list: {}", e))?;
+#[cfg(feature = "fetch")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FetchStatus {
+    /// A fresh copy was downloaded.
+    Downloaded,
+    /// The server answered 304 Not Modified and the cached copy was used.
+    Unchanged,
+}
+
+#[cfg(feature = "fetch")]
+#[derive(Default, Serialize, Deserialize)]
+struct CacheMeta {
+    etag: Option<String>,
+    last_modified: Option<String>,
+}
+
+/// Directory for cached downloads: `WORDLE_CACHE_DIR`, or `wordle-solver` in the system
+/// temp directory.
+#[cfg(feature = "fetch")]
+fn cache_dir() -> std::path::PathBuf {
+    std::env::var_os("WORDLE_CACHE_DIR")
+        .map(std::path::PathBuf::from)
+        .unwrap_or_else(|| std::env::temp_dir().join("wordle-solver"))
+}
 
-    let words: HashSet<String> = content
-        .lines()
-        .map(|s| s.trim().to_string())
-        .filter(|s| !s.is_empty())
+/// Cached body and metadata files for `url`.
+#[cfg(feature = "fetch")]
+fn cache_paths(url: &str) -> (std::path::PathBuf, std::path::PathBuf) {
+    let key: String = url
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
         .collect();
+    let dir = cache_dir();
+    (
+        dir.join(format!("{}.body", key)),
+        dir.join(format!("{}.meta", key)),
+    )
+}
+
+/// How long one download attempt may take.
+#[cfg(feature = "fetch")]
+const FETCH_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(30);
+/// Extra attempts for a download that failed transiently.
+#[cfg(feature = "fetch")]
+const FETCH_RETRIES: u32 = 2;
+
+/// Send the request `build` makes, retrying transient failures (connection errors,
+/// timeouts, 429 and 5xx responses) up to `retries` more times, waiting half a second and
+/// doubling each time. Any other response is returned for the caller to check. This hands
+/// back the response rather than its text because `fetch_cached`, which every data source
+/// downloads through, needs the 304 status and cache headers too.
+#[cfg(feature = "fetch")]
+fn send_with_retry(
+    client: &reqwest::blocking::Client,
+    build: impl Fn(&reqwest::blocking::Client) -> reqwest::blocking::RequestBuilder,
+    retries: u32,
+) -> Result<reqwest::blocking::Response, String> {
+    let mut delay = std::time::Duration::from_millis(500);
+    for _ in 0..retries {
+        match build(client).send() {
+            Ok(response)
+                if response.status().is_server_error()
+                    || response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS => {}
+            Err(e) if e.is_timeout() || e.is_connect() => {}
+            result => return result.map_err(|e| e.to_string()),
+        }
+        std::thread::sleep(delay);
+        delay *= 2;
+    }
+    build(client).send().map_err(|e| e.to_string())
+}
+
+/// GET `url`, revalidating any cached copy with If-None-Match/If-Modified-Since. A 304
+/// response returns the cached body; a fresh body is cached along with its ETag and
+/// Last-Modified headers. Any other non-2xx status is an error. Cache write failures only
+/// produce a warning.
+#[cfg(feature = "fetch")]
+fn fetch_cached(url: &str) -> Result<(String, FetchStatus), String> {
+    let dir = cache_dir();
+    let (body_path, meta_path) = cache_paths(url);
+
+    let cached_body = std::fs::read_to_string(&body_path).ok();
+    let meta: CacheMeta = cached_body
+        .as_ref()
+        .and_then(|_| std::fs::read_to_string(&meta_path).ok())
+        .and_then(|m| serde_json::from_str(&m).ok())
+        .unwrap_or_default();
+
+    let client = reqwest::blocking::Client::builder()
+        .timeout(FETCH_TIMEOUT)
+        .build()
+        .map_err(|e| e.to_string())?;
+    let response = send_with_retry(
+        &client,
+        |client| {
+            let mut request = client.get(url);
+            if let Some(etag) = &meta.etag {
+                request = request.header(reqwest::header::IF_NONE_MATCH, etag);
+            }
+            if let Some(modified) = &meta.last_modified {
+                request = request.header(reqwest::header::IF_MODIFIED_SINCE, modified);
+            }
+            request
+        },
+        FETCH_RETRIES,
+    )?;
+
+    if response.status() == reqwest::StatusCode::NOT_MODIFIED {
+        if let Some(body) = cached_body {
+            return Ok((body, FetchStatus::Unchanged));
+        }
+    }
+    // An error page must not be parsed as a word list, or cached as one
+    if !response.status().is_success() {
+        return Err(format!("HTTP {}", response.status()));
+    }
+
+    let header = |name| {
+        response
+            .headers()
+            .get(name)
+            .and_then(|v: &reqwest::header::HeaderValue| v.to_str().ok())
+            .map(str::to_string)
+    };
+    let meta = CacheMeta {
+        etag: header(reqwest::header::ETAG),
+        last_modified: header(reqwest::header::LAST_MODIFIED),
+    };
+    let body = response.text().map_err(|e| e.to_string())?;
+
+    let written = std::fs::create_dir_all(&dir)
+        .and_then(|_| std::fs::write(&body_path, &body))
+        .and_then(|_| {
+            let json = serde_json::to_string(&meta).map_err(std::io::Error::other)?;
+            std::fs::write(&meta_path, json)
+        });
+    if let Err(e) = written {
+        eprintln!("Warning: couldn't cache {}: {}", url, e);
+    }
+
+    Ok((body, FetchStatus::Downloaded))
+}
+
+/// Read a word list from an http(s) URL (through the download cache) or a local file.
+#[cfg(feature = "fetch")]
+pub fn read_word_source(location: &str) -> Result<(String, FetchStatus), String> {
+    if location.starts_with("http://") || location.starts_with("https://") {
+        fetch_cached(location)
+    } else {
+        std::fs::read_to_string(location)
+            .map(|content| (content, FetchStatus::Downloaded))
+            .map_err(|e| e.to_string())
+    }
+}
 
-    Ok(words)
+/// True if `word` is exactly five ASCII lowercase letters.
+pub fn is_valid_word(word: &str) -> bool {
+    word.len() == 5 && word.bytes().all(|b| b.is_ascii_lowercase())
+}
+
+/// True if `feedback` is five tiles, each g (green), y (yellow), x (grey) or ? (unknown).
+pub fn is_valid_feedback(feedback: &str) -> bool {
+    feedback.len() == 5 && feedback.chars().all(|c| matches!(c, 'g' | 'y' | 'x' | '?'))
+}
+
+/// Explain why typed or pasted `guess` isn't a valid word. Counts characters rather than
+/// bytes, so an accented or full-width letter is reported as such instead of skewing the
+/// length.
+pub fn check_guess_input(guess: &str) -> Result<(), String> {
+    if let Some(c) = guess.chars().find(|c| !c.is_ascii()) {
+        return Err(format!(
+            "'{}' isn't a plain letter: use a-z only (no accented or full-width characters).",
+            c
+        ));
+    }
+    let count = guess.chars().count();
+    if count != 5 {
+        return Err(format!("Guess must be exactly 5 letters (got {}).", count));
+    }
+    if !is_valid_word(guess) {
+        return Err("Guess must be exactly 5 lowercase letters.".to_string());
+    }
+    Ok(())
+}
+
+/// Explain why `feedback` isn't five g/y/x/? tiles, like `check_guess_input`.
+pub fn check_feedback_input(feedback: &str) -> Result<(), String> {
+    if let Some(c) = feedback.chars().find(|c| !c.is_ascii()) {
+        return Err(format!(
+            "'{}' isn't a feedback tile: use g, y, x or ? (no accented or full-width characters).",
+            c
+        ));
+    }
+    let count = feedback.chars().count();
+    if count != 5 {
+        return Err(format!(
+            "Feedback must be exactly 5 characters (got {}).",
+            count
+        ));
+    }
+    if !is_valid_feedback(feedback) {
+        return Err(
+            "Feedback must be exactly 5 characters, each g, y, x or ? (unknown).".to_string(),
+        );
+    }
+    Ok(())
+}
+
+/// Parse a newline-delimited word list, keeping only entries that are exactly five
+/// lowercase letters. Returns the words and the number of non-blank lines rejected.
+pub fn parse_word_list(content: &str) -> (HashSet<String>, usize) {
+    let mut words = HashSet::new();
+    let mut rejected = 0;
+
+    for line in content.lines().map(str::trim).filter(|s| !s.is_empty()) {
+        if is_valid_word(line) {
+            words.insert(line.to_string());
+        } else {
+            rejected += 1;
+        }
+    }
+
+    (words, rejected)
+}
+
+/// Parse a word list that is either a JSON array of strings (the format of the NYT game's
+/// `wordlist` and `wordlist_solutions`) or newline-delimited. JSON entries are trimmed and
+/// lowercased; like `parse_word_list`, returns the words and the number rejected.
+pub fn parse_word_source(content: &str) -> (HashSet<String>, usize) {
+    if !content.trim_start().starts_with('[') {
+        return parse_word_list(content);
+    }
+    let Ok(entries) = serde_json::from_str::<Vec<String>>(content) else {
+        return parse_word_list(content);
+    };
+
+    let mut words = HashSet::new();
+    let mut rejected = 0;
+    for entry in entries {
+        let word = entry.trim().to_ascii_lowercase();
+        if is_valid_word(&word) {
+            words.insert(word);
+        } else {
+            rejected += 1;
+        }
+    }
+    (words, rejected)
 }
 
 pub struct FrequencyData {
@@ -75,55 +543,110 @@ pub struct FrequencyData {
     pub dictionary: HashSet<String>,
 }
 
-pub fn load_frequency_data(words: &HashSet<&String>) -> FrequencyData {
-    let content = match reqwest::blocking::get(
-        "https://raw.githubusercontent.com/hermitdave/FrequencyWords/master/content/2018/en/en_50k.txt",
-    ) {
-        Ok(r) => match r.text() {
-            Ok(t) => t,
+#[cfg(feature = "fetch")]
+const DEFAULT_FREQUENCY_URL: &str =
+    "https://raw.githubusercontent.com/hermitdave/FrequencyWords/master/content/2018/en/en_50k.txt";
+
+/// Word frequency sources in precedence order: the comma-separated URLs or files in
+/// `WORDLE_FREQUENCY_SOURCES`, or else the OpenSubtitles 50k list.
+#[cfg(feature = "fetch")]
+pub fn frequency_sources() -> Vec<String> {
+    match std::env::var("WORDLE_FREQUENCY_SOURCES") {
+        Ok(list) if !list.trim().is_empty() => list
+            .split(',')
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+            .collect(),
+        _ => vec![DEFAULT_FREQUENCY_URL.to_string()],
+    }
+}
+
+/// Load and merge `sources` (see `merge_frequency_data`). Sources that fail to load are
+/// skipped with a warning.
+#[cfg(feature = "fetch")]
+pub fn load_frequency_data(sources: &[String], words: &HashSet<&String>) -> FrequencyData {
+    let loaded: Vec<FrequencyData> = sources
+        .iter()
+        .filter_map(|source| match read_word_source(source) {
+            Ok((content, _)) => Some(parse_frequency_data(&content, words)),
             Err(e) => {
-                eprintln!("Warning: couldn't read word frequency data: {}.", e);
-                return FrequencyData {
-                    commonality: HashMap::new(),
-                    dictionary: HashSet::new(),
-                };
+                eprintln!(
+                    "Warning: couldn't fetch word frequency data {}: {}",
+                    source, e
+                );
+                None
             }
-        },
-        Err(e) => {
-            eprintln!("Warning: couldn't fetch word frequency data: {}. Commonality scoring disabled.", e);
-            return FrequencyData {
-                commonality: HashMap::new(),
-                dictionary: HashSet::new(),
-            };
-        }
+        })
+        .collect();
+    if loaded.is_empty() {
+        eprintln!("Warning: no word frequency data loaded. Commonality scoring disabled.");
+    }
+    merge_frequency_data(loaded)
+}
+
+/// Combine parsed frequency sources. Each word takes its commonality from the first source
+/// that has it, so a small, trusted list can lead and a larger one fill its gaps; the
+/// dictionaries are combined.
+pub fn merge_frequency_data(sources: Vec<FrequencyData>) -> FrequencyData {
+    let mut merged = FrequencyData {
+        commonality: HashMap::new(),
+        dictionary: HashSet::new(),
     };
+    for source in sources {
+        for (word, score) in source.commonality {
+            merged.commonality.entry(word).or_insert(score);
+        }
+        merged.dictionary.extend(source.dictionary);
+    }
+    merged
+}
+
+/// Normalize a frequency-list token: strip trailing non-letters (e.g. "word." or "word'")
+/// and reject tokens with non-letters inside (e.g. "don't"). Returns the lowercased word.
+fn normalize_frequency_token(token: &str) -> Option<String> {
+    let word = token.trim_end_matches(|c: char| !c.is_ascii_alphabetic());
+    if word.is_empty() || !word.chars().all(|c| c.is_ascii_alphabetic()) {
+        return None;
+    }
+    Some(word.to_ascii_lowercase())
+}
 
+/// Parse "word count" lines into commonality scores (for `words` only) and a dictionary.
+pub fn parse_frequency_data(content: &str, words: &HashSet<&String>) -> FrequencyData {
     let mut raw: HashMap<String, f64> = HashMap::new();
     let mut max_freq: f64 = 0.0;
     let mut dictionary = HashSet::new();
 
     for line in content.lines() {
-        let Some((word, count_str)) = line.split_once(' ') else {
+        let Some((token, count_str)) = line.split_once(' ') else {
+            continue;
+        };
+        let Some(word) = normalize_frequency_token(token) else {
             continue;
         };
         // Build dictionary of all words (used for plural detection)
-        dictionary.insert(word.to_string());
+        dictionary.insert(word.clone());
 
-        if word.len() != 5 || !words.contains(&word.to_string()) {
+        if word.len() != 5 || !words.contains(&word) {
             continue;
         }
-        if let Ok(count) = count_str.parse::<f64>() {
+        if let Ok(count) = count_str.trim().parse::<f64>() {
             if count > max_freq {
                 max_freq = count;
             }
-            raw.insert(word.to_string(), count);
+            // Keep the first (highest) count if a stripped token repeats an earlier word
+            raw.entry(word).or_insert(count);
         }
     }
 
-    if max_freq > 0.0 {
+    // Log normalization needs a top count above 1 (ln 1 = 0). Without one there is no
+    // usable scale, so return no commonality at all rather than raw counts.
+    if max_freq <= 1.0 {
+        raw.clear();
+    } else {
         let log_max = max_freq.ln();
         for freq in raw.values_mut() {
-            *freq = freq.ln() / log_max;
+            *freq = freq.max(1.0).ln() / log_max;
         }
     }
 
@@ -181,13 +704,42 @@ pub fn filter_regular_plurals(words: &mut Vec<String>, dictionary: &HashSet<Stri
     before - words.len()
 }
 
+/// Remove words that are valid guesses but never official answers (e.g. vulgar words or
+/// variant spellings), as listed in a user-maintained file. Returns the count removed.
+pub fn filter_never_answers(words: &mut Vec<String>, never_answers: &HashSet<String>) -> usize {
+    let before = words.len();
+    words.retain(|w| !never_answers.contains(w));
+    before - words.len()
+}
+
+/// Keep only words from the user's own "known words" list, so suggestions never include
+/// valid-but-obscure words. Returns the count removed.
+pub fn filter_known_words(words: &mut Vec<String>, known_words: &HashSet<String>) -> usize {
+    let before = words.len();
+    words.retain(|w| known_words.contains(w));
+    before - words.len()
+}
+
 // ---------- Game state & constraints ----------
 
+#[derive(Clone, Serialize, Deserialize)]
 pub struct GameState {
     pub greens: [Option<char>; 5],
     pub yellows_not_at: [HashSet<char>; 5],
     pub required_letters: HashSet<char>,
     pub excluded_letters: HashSet<char>,
+    /// Minimum number of times each required letter appears, from the most green/yellow
+    /// copies of it seen in a single guess (e.g. two yellow E's means at least two E's).
+    #[serde(default)]
+    pub min_counts: HashMap<char, usize>,
+    /// Maximum number of times a letter appears, known once a guess shows a grey copy of a
+    /// letter alongside green/yellow copies (e.g. "speed" with one yellow and one grey E).
+    #[serde(default)]
+    pub max_counts: HashMap<char, usize>,
+    /// Words already guessed without winning, kept out of suggestions. Usually the
+    /// feedback rules them out anyway, but not when it has unknown ('?') tiles.
+    #[serde(default)]
+    pub guessed: HashSet<String>,
 }
 
 impl GameState {
@@ -197,12 +749,99 @@ impl GameState {
             yellows_not_at: Default::default(),
             required_letters: HashSet::new(),
             excluded_letters: HashSet::new(),
+            min_counts: HashMap::new(),
+            max_counts: HashMap::new(),
+            guessed: HashSet::new(),
+        }
+    }
+
+    /// Fix `letter` at `pos` directly (without a guess). Errors on a contradiction.
+    pub fn set_green(&mut self, pos: usize, letter: char) -> Result<(), String> {
+        check_letter(letter)?;
+        let pos_name = pos + 1;
+        match self.greens.get(pos) {
+            None => return Err(format!("Position {} is out of range (1-5)", pos_name)),
+            Some(Some(existing)) if *existing != letter => {
+                return Err(format!(
+                    "Position {} is already green with '{}'",
+                    pos_name, existing
+                ))
+            }
+            _ => {}
         }
+        if self.excluded_letters.contains(&letter) {
+            return Err(format!("'{}' is excluded", letter));
+        }
+        if self.yellows_not_at[pos].contains(&letter) {
+            return Err(format!(
+                "'{}' is ruled out at position {}",
+                letter, pos_name
+            ));
+        }
+        self.greens[pos] = Some(letter);
+        self.required_letters.insert(letter);
+        Ok(())
+    }
+
+    /// Record that `letter` is in the word but not at `pos`. Errors on a contradiction.
+    pub fn add_yellow(&mut self, pos: usize, letter: char) -> Result<(), String> {
+        check_letter(letter)?;
+        if pos >= 5 {
+            return Err(format!("Position {} is out of range (1-5)", pos + 1));
+        }
+        if self.greens[pos] == Some(letter) {
+            return Err(format!("'{}' is green at position {}", letter, pos + 1));
+        }
+        self.require(letter)?;
+        self.yellows_not_at[pos].insert(letter);
+        Ok(())
+    }
+
+    /// Record that `letter` is somewhere in the word. Errors if it is excluded.
+    pub fn require(&mut self, letter: char) -> Result<(), String> {
+        check_letter(letter)?;
+        if self.excluded_letters.contains(&letter) {
+            return Err(format!("'{}' is both required and excluded", letter));
+        }
+        self.required_letters.insert(letter);
+        Ok(())
+    }
+
+    /// Record that `letter` is not in the word. Errors if it is required.
+    pub fn exclude(&mut self, letter: char) -> Result<(), String> {
+        check_letter(letter)?;
+        if self.required_letters.contains(&letter) {
+            return Err(format!("'{}' is both required and excluded", letter));
+        }
+        self.excluded_letters.insert(letter);
+        Ok(())
+    }
+
+    /// True once any feedback has been applied.
+    pub fn has_constraints(&self) -> bool {
+        self.greens.iter().any(Option::is_some)
+            || self.yellows_not_at.iter().any(|s| !s.is_empty())
+            || !self.required_letters.is_empty()
+            || !self.excluded_letters.is_empty()
+    }
+
+    /// Positions where `letter` may still appear: not green with another letter and not
+    /// ruled out by a yellow or grey at that position.
+    pub fn allowed_positions(&self, letter: char) -> Vec<usize> {
+        (0..5)
+            .filter(|&i| {
+                self.greens[i].is_none_or(|g| g == letter)
+                    && !self.yellows_not_at[i].contains(&letter)
+            })
+            .collect()
     }
 
     pub fn update(&mut self, guess: &str, feedback: &str) {
         let guess_chars: Vec<char> = guess.chars().collect();
         let feedback_chars: Vec<char> = feedback.chars().collect();
+        if feedback != "ggggg" {
+            self.guessed.insert(guess.to_string());
+        }
 
         // Pass 1: greens and yellows (so required_letters is populated before grey check)
         for i in 0..5 {
@@ -222,16 +861,45 @@ impl GameState {
             }
         }
 
+        // Unknown ('?') tiles impose nothing, but a grey can't exclude a letter whose
+        // other copy in this guess might have been green or yellow
+        let mut unknown: HashMap<char, usize> = HashMap::new();
+        for (&letter, &fb) in guess_chars.iter().zip(&feedback_chars).take(5) {
+            if fb == '?' {
+                *unknown.entry(letter).or_insert(0) += 1;
+            }
+        }
+
         // Pass 2: greys
         for i in 0..5 {
             let letter = guess_chars[i];
             if feedback_chars[i] == 'x' {
-                if !self.required_letters.contains(&letter) {
+                if !self.required_letters.contains(&letter) && !unknown.contains_key(&letter) {
                     self.excluded_letters.insert(letter);
                 }
                 self.yellows_not_at[i].insert(letter);
             }
         }
+
+        // Each green/yellow copy of a letter in one guess is a distinct occurrence
+        let mut present: HashMap<char, usize> = HashMap::new();
+        for (&letter, &fb) in guess_chars.iter().zip(&feedback_chars).take(5) {
+            if matches!(fb, 'g' | 'y') {
+                *present.entry(letter).or_insert(0) += 1;
+            }
+        }
+        for (&letter, &fb) in guess_chars.iter().zip(&feedback_chars).take(5) {
+            let count = present.get(&letter).copied().unwrap_or(0)
+                + unknown.get(&letter).copied().unwrap_or(0);
+            if fb == 'x' && count > 0 {
+                let max = self.max_counts.entry(letter).or_insert(count);
+                *max = (*max).min(count);
+            }
+        }
+        for (letter, count) in present {
+            let min = self.min_counts.entry(letter).or_insert(0);
+            *min = (*min).max(count);
+        }
     }
 
     pub fn matches(&self, word: &str) -> bool {
@@ -257,6 +925,23 @@ impl GameState {
             }
         }
 
+        for (&letter, &max) in &self.max_counts {
+            if chars.iter().filter(|&&c| c == letter).count() > max {
+                return false;
+            }
+        }
+
+        for (&letter, &min) in &self.min_counts {
+            let placeable = self
+                .allowed_positions(letter)
+                .into_iter()
+                .filter(|&i| chars.get(i) == Some(&letter))
+                .count();
+            if placeable < min {
+                return false;
+            }
+        }
+
         for &ch in &chars {
             if self.excluded_letters.contains(&ch) {
                 return false;
@@ -266,117 +951,2400 @@ impl GameState {
         true
     }
 
-    pub fn display(&self) {
-        let green_str: String = (0..5)
-            .map(|i| match self.greens[i] {
-                Some(c) => c.to_ascii_uppercase(),
-                None => '_',
-            })
-            .collect();
-        println!("  Green:    {}", green_str);
-
-        let required: String = self.required_letters.iter().sorted().collect();
-        if !required.is_empty() {
-            println!("  Required: {}", required);
-        }
-
-        let excluded: String = self.excluded_letters.iter().sorted().collect();
-        if !excluded.is_empty() {
-            println!("  Excluded: {}", excluded);
-        }
+    /// True if nothing is known about `letter` yet (not green, required or excluded).
+    pub fn is_untested(&self, letter: char) -> bool {
+        !self.required_letters.contains(&letter)
+            && !self.excluded_letters.contains(&letter)
+            && !self.greens.contains(&Some(letter))
     }
 
-    pub fn green_display(&self) -> String {
-        (0..5)
-            .map(|i| match self.greens[i] {
-                Some(c) => c.to_ascii_uppercase(),
-                None => '_',
+    /// Every letter's status, like the on-screen keyboard in Wordle.
+    pub fn letter_statuses(&self) -> HashMap<char, LetterStatus> {
+        ('a'..='z')
+            .map(|letter| {
+                let status = if self.greens.contains(&Some(letter)) {
+                    LetterStatus::Green
+                } else if self.required_letters.contains(&letter) {
+                    LetterStatus::Yellow
+                } else if self.excluded_letters.contains(&letter) {
+                    LetterStatus::Grey
+                } else {
+                    LetterStatus::Untested
+                };
+                (letter, status)
             })
             .collect()
     }
 
-    pub fn required_display(&self) -> String {
-        self.required_letters.iter().sorted().collect()
+    /// How much new information `word` probes for: the number of distinct letters it
+    /// contains that are not yet green, required or excluded, and the number of
+    /// still-unknown (non-green) positions holding one of those letters.
+    pub fn letter_coverage(&self, word: &str) -> (usize, usize) {
+        let new_letters = word
+            .chars()
+            .filter(|&c| self.is_untested(c))
+            .unique()
+            .count();
+        let positions = word
+            .chars()
+            .zip(&self.greens)
+            .filter(|(c, green)| green.is_none() && self.is_untested(*c))
+            .count();
+        (new_letters, positions)
     }
 
-    pub fn excluded_display(&self) -> String {
-        self.excluded_letters.iter().sorted().collect()
-    }
-}
+    /// Classify how much a guess could teach: `Wasteful` if every letter is already green
+    /// in that spot, excluded, or a known letter in a spot already ruled out for it;
+    /// `HighInfo` if it tests four or more new letters.
+    pub fn guess_value(&self, guess: &str) -> GuessValue {
+        let (new_letters, _) = self.letter_coverage(guess);
+        if new_letters >= 4 {
+            return GuessValue::HighInfo;
+        }
 
-impl Default for GameState {
-    fn default() -> Self {
-        Self::new()
+        let probes_something = guess.chars().enumerate().any(|(i, c)| {
+            if self.greens[i] == Some(c) || self.excluded_letters.contains(&c) {
+                return false;
+            }
+            !self.required_letters.contains(&c) || self.allowed_positions(c).contains(&i)
+        });
+        if probes_something {
+            GuessValue::Normal
+        } else {
+            GuessValue::Wasteful
+        }
     }
-}
 
-// ---------- Scoring ----------
+    /// Copies of this state with one constraint dropped, each with a description of the
+    /// dropped constraint ("the green at position 3").
+    pub fn relaxations(&self) -> Vec<(String, GameState)> {
+        let mut relaxed = Vec::new();
 
-pub fn letter_presence_frequency(words: &[&String]) -> HashMap<char, f64> {
-    let mut counts: HashMap<char, u32> = HashMap::new();
-    let total = words.len() as f64;
+        for (i, green) in self.greens.iter().enumerate() {
+            if let Some(letter) = *green {
+                let mut state = self.clone();
+                state.greens[i] = None;
+                if let Some(min) = state.min_counts.get_mut(&letter) {
+                    *min = min.saturating_sub(1);
+                }
+                let still_placed = state.greens.contains(&Some(letter))
+                    || state.min_counts.get(&letter).is_some_and(|&m| m > 0);
+                if !still_placed {
+                    state.required_letters.remove(&letter);
+                }
+                relaxed.push((format!("the green at position {}", i + 1), state));
+            }
+        }
 
-    for word in words {
-        let unique: HashSet<char> = word.chars().collect();
-        for ch in unique {
-            *counts.entry(ch).or_insert(0) += 1;
+        for (i, letters) in self.yellows_not_at.iter().enumerate() {
+            for &letter in letters.iter().sorted() {
+                let mut state = self.clone();
+                state.yellows_not_at[i].remove(&letter);
+                relaxed.push((
+                    format!("the yellow/grey '{}' at position {}", letter, i + 1),
+                    state,
+                ));
+            }
         }
-    }
 
-    counts
-        .into_iter()
-        .map(|(ch, count)| (ch, count as f64 / total))
-        .collect()
-}
+        for &letter in self.required_letters.iter().sorted() {
+            if self.greens.contains(&Some(letter)) {
+                continue;
+            }
+            let mut state = self.clone();
+            state.required_letters.remove(&letter);
+            state.min_counts.remove(&letter);
+            relaxed.push((format!("the requirement for '{}'", letter), state));
+        }
 
-pub fn score_word(word: &str, freq: &HashMap<char, f64>) -> f64 {
-    let unique: HashSet<char> = word.chars().collect();
-    unique.iter().filter_map(|ch| freq.get(ch)).sum()
-}
+        for &letter in self.excluded_letters.iter().sorted() {
+            let mut state = self.clone();
+            state.excluded_letters.remove(&letter);
+            relaxed.push((format!("the exclusion of '{}'", letter), state));
+        }
 
-pub fn rank_words<'a>(
+        for &letter in self.max_counts.keys().sorted() {
+            let mut state = self.clone();
+            state.max_counts.remove(&letter);
+            relaxed.push((format!("the limit on repeated '{}'", letter), state));
+        }
+
+        relaxed
+    }
+
+    /// When nothing in `pool` matches, find the single constraint whose removal restores
+    /// the most words: likely a feedback tile entered wrong. Returns its description and
+    /// the number of words it would restore.
+    pub fn diagnose_no_matches(&self, pool: &[&String]) -> Option<(String, usize)> {
+        self.relaxations()
+            .into_iter()
+            .map(|(description, state)| {
+                let restored = pool.iter().filter(|w| state.matches(w)).count();
+                (description, restored)
+            })
+            .filter(|(_, restored)| *restored > 0)
+            .max_by_key(|(_, restored)| *restored)
+    }
+
+    pub fn display(&self) {
+        for line in self.constraint_lines() {
+            println!("{}", line);
+        }
+    }
+
+    /// The constraint summary printed by `display`, one indented line per entry.
+    pub fn constraint_lines(&self) -> Vec<String> {
+        let mut lines = vec![format!("  Green:    {}", self.green_display())];
+
+        let required = self.required_display();
+        if !required.is_empty() {
+            lines.push(format!("  Required: {}", required));
+        }
+
+        let excluded = self.excluded_display();
+        if !excluded.is_empty() {
+            lines.push(format!("  Excluded: {}", excluded));
+        }
+        lines
+    }
+
+    /// One-line summary for status bars, e.g. `12 left | green _R_N_ | req AEN | excl BCDF`.
+    pub fn status_line(&self, candidate_count: usize) -> String {
+        let mut parts = vec![
+            format!("{} left", candidate_count),
+            format!("green {}", self.green_display()),
+        ];
+
+        let required = self.required_display();
+        if !required.is_empty() {
+            parts.push(format!("req {}", required.to_ascii_uppercase()));
+        }
+
+        let excluded = self.excluded_display();
+        if !excluded.is_empty() {
+            parts.push(format!("excl {}", excluded.to_ascii_uppercase()));
+        }
+        parts.join(" | ")
+    }
+
+    /// The greens and per-position exclusions as a regex usable with grep (POSIX or PCRE),
+    /// e.g. `^[^aes]r[^aeos][^aes][^aes]$`. Required letters and letter counts can't be
+    /// expressed per position, so they are left out (see `regex_caveat`).
+    pub fn to_regex(&self) -> String {
+        let positions: String = (0..5)
+            .map(|i| match self.greens[i] {
+                Some(c) => c.to_string(),
+                None => {
+                    let banned: String = self
+                        .excluded_letters
+                        .iter()
+                        .chain(&self.yellows_not_at[i])
+                        .sorted()
+                        .dedup()
+                        .collect();
+                    if banned.is_empty() {
+                        ".".to_string()
+                    } else {
+                        format!("[^{}]", banned)
+                    }
+                }
+            })
+            .collect();
+        format!("^{}$", positions)
+    }
+
+    /// What `to_regex` can't check, e.g. `also requires: e, t`, or `None` when the regex
+    /// alone is exact apart from letter counts.
+    pub fn regex_caveat(&self) -> Option<String> {
+        let unplaced: Vec<char> = self
+            .required_letters
+            .iter()
+            .filter(|l| !self.greens.contains(&Some(**l)))
+            .copied()
+            .sorted()
+            .collect();
+        if unplaced.is_empty() {
+            return None;
+        }
+        Some(format!("also requires: {}", unplaced.iter().join(", ")))
+    }
+
+    pub fn green_display(&self) -> String {
+        (0..5)
+            .map(|i| match self.greens[i] {
+                Some(c) => c.to_ascii_uppercase(),
+                None => '_',
+            })
+            .collect()
+    }
+
+    pub fn required_display(&self) -> String {
+        self.required_letters.iter().sorted().collect()
+    }
+
+    pub fn excluded_display(&self) -> String {
+        self.excluded_letters.iter().sorted().collect()
+    }
+}
+
+/// How informative a guess is under the current constraints (see `GameState::guess_value`).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GuessValue {
+    Wasteful,
+    Normal,
+    HighInfo,
+}
+
+/// What is known about a letter (see `GameState::letter_statuses`).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LetterStatus {
+    /// Placed in at least one position.
+    Green,
+    /// In the word, but not yet placed.
+    Yellow,
+    /// Not in the word.
+    Grey,
+    Untested,
+}
+
+fn check_letter(letter: char) -> Result<(), String> {
+    if letter.is_ascii_lowercase() {
+        Ok(())
+    } else {
+        Err(format!("'{}' is not a lowercase letter", letter))
+    }
+}
+
+impl Default for GameState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A letter one guess showed as absent (every copy grey) and another as present
+/// (green or yellow): the feedback can't all be right.
+pub struct LetterConflict {
+    pub letter: char,
+    pub grey_in: String,
+    pub present_in: String,
+}
+
+impl std::fmt::Display for LetterConflict {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "'{}' is grey in {} but green or yellow in {} -- one of those feedbacks is probably wrong",
+            self.letter.to_ascii_uppercase(),
+            self.grey_in.to_ascii_uppercase(),
+            self.present_in.to_ascii_uppercase()
+        )
+    }
+}
+
+/// Check a guess history for a letter that is both required and excluded.
+/// `GameState::update` silently lets the later guess win such a clash (a green or yellow
+/// lifts an earlier exclusion, and a grey can't exclude a required letter), so the
+/// required and excluded sets never overlap; comparing the guesses themselves finds it.
+pub fn find_letter_conflict(guesses: &[(String, String)]) -> Option<LetterConflict> {
+    let with_feedback = |guess: &str, feedback: &str, marks: &[char]| -> HashSet<char> {
+        guess
+            .chars()
+            .zip(feedback.chars())
+            .filter(|(_, fb)| marks.contains(fb))
+            .map(|(letter, _)| letter)
+            .collect()
+    };
+
+    for (grey_guess, grey_feedback) in guesses {
+        // Only letters with no green, yellow or unknown copy in this guess are absent
+        let shown = with_feedback(grey_guess, grey_feedback, &['g', 'y', '?']);
+        let absent = with_feedback(grey_guess, grey_feedback, &['x']);
+        for (guess, feedback) in guesses {
+            let present = with_feedback(guess, feedback, &['g', 'y']);
+            if let Some(letter) = grey_guess
+                .chars()
+                .find(|c| absent.contains(c) && !shown.contains(c) && present.contains(c))
+            {
+                return Some(LetterConflict {
+                    letter,
+                    grey_in: grey_guess.clone(),
+                    present_in: guess.clone(),
+                });
+            }
+        }
+    }
+    None
+}
+
+/// Check guesses against letters set directly at known positions (0-based), e.g. from a
+/// crossword clue. A guess conflicts when it puts a different letter there and gets green,
+/// or puts the known letter there and doesn't.
+pub fn known_letter_conflict(
+    known: &[(usize, char)],
+    guesses: &[(String, String)],
+) -> Option<String> {
+    for (guess, feedback) in guesses {
+        for &(pos, letter) in known {
+            let (Some(g), Some(f)) = (guess.chars().nth(pos), feedback.chars().nth(pos)) else {
+                continue;
+            };
+            let clash = match f {
+                'g' => g != letter,
+                'y' | 'x' => g == letter,
+                _ => false,
+            };
+            if clash {
+                return Some(format!(
+                    "position {} was set to '{}', but {} got {} there -- check that tile",
+                    pos + 1,
+                    letter.to_ascii_uppercase(),
+                    guess.to_ascii_uppercase(),
+                    match f {
+                        'g' => "green",
+                        'y' => "yellow",
+                        _ => "grey",
+                    }
+                ));
+            }
+        }
+    }
+    None
+}
+
+// ---------- Feedback ----------
+
+/// The feedback string (g/y/x) Wordle shows for `guess` when the answer is `answer`.
+/// Duplicate letters are handled like the game: greens are assigned first, then yellows
+/// left to right only while unmatched copies of the letter remain in the answer.
+pub fn feedback_for(guess: &str, answer: &str) -> String {
+    let guess_chars: Vec<char> = guess.chars().collect();
+    let answer_chars: Vec<char> = answer.chars().collect();
+    let mut result = vec!['x'; guess_chars.len()];
+    let mut unmatched: HashMap<char, u32> = HashMap::new();
+
+    for (i, &ch) in answer_chars.iter().enumerate() {
+        if guess_chars.get(i) == Some(&ch) {
+            result[i] = 'g';
+        } else {
+            *unmatched.entry(ch).or_insert(0) += 1;
+        }
+    }
+
+    for (i, &ch) in guess_chars.iter().enumerate() {
+        if result[i] == 'g' {
+            continue;
+        }
+        if let Some(count) = unmatched.get_mut(&ch).filter(|c| **c > 0) {
+            *count -= 1;
+            result[i] = 'y';
+        }
+    }
+
+    result.into_iter().collect()
+}
+
+/// Whether a guess looks mis-entered: some tile came back green or yellow, yet it didn't
+/// eliminate a single candidate (`before` and `after` are the counts around the guess).
+pub fn ineffective_guess(before: usize, after: usize, feedback: &str) -> bool {
+    before == after && feedback != "ggggg" && feedback.chars().any(|c| c == 'g' || c == 'y')
+}
+
+/// A notice when `guess` with `feedback` repeats an earlier entry in `guesses`: the same
+/// guess and feedback as the last one (probably submitted twice by accident), or a guess
+/// already entered with different feedback, which can't both be right.
+pub fn repeated_guess(guesses: &[(String, String)], guess: &str, feedback: &str) -> Option<String> {
+    if let Some((_, earlier)) = guesses.iter().find(|(g, f)| g == guess && f != feedback) {
+        return Some(format!(
+            "'{}' was already entered with feedback {}; the same word can't score {} against the same answer",
+            guess, earlier, feedback
+        ));
+    }
+    guesses
+        .last()
+        .filter(|(g, f)| g == guess && f == feedback)
+        .map(|_| {
+            format!(
+                "'{}' with {} repeats the previous guess, so it adds nothing",
+                guess, feedback
+            )
+        })
+}
+
+/// Check that a recorded game is consistent with `answer`: every feedback string must be
+/// exactly what `feedback_for` gives, and the game must end with the answer scored "ggggg".
+/// The error names the first inconsistent step (guesses count from 1).
+pub fn verify_path(guesses: &[(String, String)], answer: &str) -> Result<(), String> {
+    let Some((last_guess, _)) = guesses.last() else {
+        return Err("No guesses to verify".to_string());
+    };
+
+    for (i, (guess, feedback)) in guesses.iter().enumerate() {
+        let expected = feedback_for(guess, answer);
+        if feedback.to_ascii_lowercase() != expected {
+            return Err(format!(
+                "Guess {} ({}): feedback {} doesn't match {} for answer {}",
+                i + 1,
+                guess,
+                feedback,
+                expected,
+                answer
+            ));
+        }
+        if guess == answer && i + 1 < guesses.len() {
+            return Err(format!(
+                "Guess {} ({}) already solved the game, but {} more guesses follow",
+                i + 1,
+                guess,
+                guesses.len() - i - 1
+            ));
+        }
+    }
+
+    if last_guess != answer {
+        return Err(format!(
+            "The last guess ({}) isn't the answer {}",
+            last_guess, answer
+        ));
+    }
+    Ok(())
+}
+
+/// `feedback_for` encoded as a base-3 integer (x=0, y=1, g=2, first letter most
+/// significant), computed without allocating. Both words must be five lowercase letters.
+pub fn feedback_code(guess: &str, answer: &str) -> u8 {
+    let guess = guess.as_bytes();
+    let answer = answer.as_bytes();
+    let mut marks = [0u8; 5];
+    let mut unmatched = [0u8; 26];
+
+    for i in 0..5 {
+        if guess[i] == answer[i] {
+            marks[i] = 2;
+        } else {
+            unmatched[(answer[i] - b'a') as usize] += 1;
+        }
+    }
+    for i in 0..5 {
+        let letter = (guess[i] - b'a') as usize;
+        if marks[i] == 0 && unmatched[letter] > 0 {
+            unmatched[letter] -= 1;
+            marks[i] = 1;
+        }
+    }
+
+    marks.iter().fold(0, |code, &m| code * 3 + m)
+}
+
+/// The g/y/x feedback string for a `feedback_code` value.
+pub fn feedback_from_code(code: u8) -> String {
+    let mut marks = ['x'; 5];
+    let mut rest = code;
+    for mark in marks.iter_mut().rev() {
+        *mark = match rest % 3 {
+            2 => 'g',
+            1 => 'y',
+            _ => 'x',
+        };
+        rest /= 3;
+    }
+    marks.iter().collect()
+}
+
+/// Row-major guess × answer matrix of `feedback_code` values.
+pub fn feedback_matrix(guesses: &[&String], answers: &[&String]) -> Vec<u8> {
+    guesses
+        .iter()
+        .flat_map(|g| answers.iter().map(move |a| feedback_code(g, a)))
+        .collect()
+}
+
+// ---------- Scoring ----------
+
+pub fn letter_presence_frequency(words: &[&String]) -> HashMap<char, f64> {
+    let mut counts: HashMap<char, u32> = HashMap::new();
+    let total = words.len() as f64;
+
+    for word in words {
+        let unique: HashSet<char> = word.chars().collect();
+        for ch in unique {
+            *counts.entry(ch).or_insert(0) += 1;
+        }
+    }
+
+    counts
+        .into_iter()
+        .map(|(ch, count)| (ch, count as f64 / total))
+        .collect()
+}
+
+/// Untested letters (not green, required or excluded) ranked by how evenly testing them
+/// would split `candidates`: 1.0 when exactly half contain the letter, falling to 0.0 when
+/// none or all do. Letters that split nothing are left out.
+pub fn letter_split_values(candidates: &[&String], state: &GameState) -> Vec<(char, f64)> {
+    letter_presence_frequency(candidates)
+        .into_iter()
+        .filter(|(c, _)| state.is_untested(*c))
+        .map(|(c, share)| (c, split_value(share)))
+        .filter(|(_, value)| *value > 0.0)
+        .sorted_by(|a, b| {
+            b.1.partial_cmp(&a.1)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then(a.0.cmp(&b.0))
+        })
+        .collect()
+}
+
+/// Which letters are still in play across a candidate set, for judging whether a
+/// probe guess is worthwhile: few varying letters means little left to probe.
+pub struct LetterSpread {
+    /// Distinct letters at the positions that are not locked, alphabetical.
+    pub varying_letters: Vec<char>,
+    /// Positions (0-based) that are green or share one letter across every candidate.
+    pub locked_positions: Vec<usize>,
+}
+
+impl LetterSpread {
+    /// One-line summary, e.g. "6 distinct letters vary across 9 candidates (positions
+    /// 1, 2, 5 locked)".
+    pub fn summary(&self, candidate_count: usize) -> String {
+        let mut line = format!(
+            "{} distinct letter{} vary across {} candidate{}",
+            self.varying_letters.len(),
+            if self.varying_letters.len() == 1 {
+                ""
+            } else {
+                "s"
+            },
+            candidate_count,
+            if candidate_count == 1 { "" } else { "s" }
+        );
+        if !self.locked_positions.is_empty() {
+            line.push_str(&format!(
+                " (position{} {} locked)",
+                if self.locked_positions.len() == 1 {
+                    ""
+                } else {
+                    "s"
+                },
+                self.locked_positions.iter().map(|i| i + 1).join(", ")
+            ));
+        }
+        line
+    }
+}
+
+/// The letters still varying across `candidates` and the positions already locked,
+/// either by a green in `state` or because every candidate agrees there.
+pub fn letter_spread(candidates: &[&String], state: &GameState) -> LetterSpread {
+    let words: Vec<Vec<char>> = candidates.iter().map(|w| w.chars().collect()).collect();
+    let locked_positions: Vec<usize> = (0..5)
+        .filter(|&i| {
+            state.greens[i].is_some()
+                || words
+                    .first()
+                    .is_some_and(|first| words.iter().all(|w| w[i] == first[i]))
+        })
+        .collect();
+    let varying_letters = words
+        .iter()
+        .flat_map(|w| {
+            (0..5)
+                .filter(|i| !locked_positions.contains(i))
+                .map(move |i| w[i])
+        })
+        .sorted()
+        .dedup()
+        .collect();
+
+    LetterSpread {
+        varying_letters,
+        locked_positions,
+    }
+}
+
+/// Fraction of words containing each adjacent letter pair (counted once per word).
+pub fn bigram_frequency(words: &[&String]) -> HashMap<(char, char), f64> {
+    let mut counts: HashMap<(char, char), u32> = HashMap::new();
+    let total = words.len() as f64;
+
+    for word in words {
+        let unique: HashSet<(char, char)> = word.chars().tuple_windows().collect();
+        for pair in unique {
+            *counts.entry(pair).or_insert(0) += 1;
+        }
+    }
+
+    counts
+        .into_iter()
+        .map(|(pair, count)| (pair, count as f64 / total))
+        .collect()
+}
+
+/// True if the word contains an adjacent letter pair rarer than `threshold`.
+pub fn has_rare_bigram(word: &str, bigrams: &HashMap<(char, char), f64>, threshold: f64) -> bool {
+    word.chars()
+        .tuple_windows()
+        .any(|pair| bigrams.get(&pair).copied().unwrap_or(0.0) < threshold)
+}
+
+pub fn score_word(word: &str, freq: &HashMap<char, f64>) -> f64 {
+    let unique: HashSet<char> = word.chars().collect();
+    unique.iter().filter_map(|ch| freq.get(ch)).sum()
+}
+
+/// Like `score_word`, but each distinct letter is worth how evenly it splits the words
+/// `freq` was built from: 1.0 for a letter in exactly half of them, falling to 0.0 for
+/// one in all or none, which testing can't learn anything from.
+pub fn letter_split_score(word: &str, freq: &HashMap<char, f64>) -> f64 {
+    let unique: HashSet<char> = word.chars().collect();
+    unique
+        .iter()
+        .filter_map(|ch| freq.get(ch))
+        .map(|&share| split_value(share))
+        .sum()
+}
+
+/// How evenly a letter found in `share` of the words splits them: 1.0 at one half, 0.0
+/// at none or all.
+fn split_value(share: f64) -> f64 {
+    1.0 - (2.0 * share - 1.0).abs()
+}
+
+/// Fraction of words with each letter at each position.
+pub fn positional_frequency(words: &[&String]) -> [HashMap<char, f64>; 5] {
+    let mut counts: [HashMap<char, u32>; 5] = Default::default();
+    let total = words.len() as f64;
+
+    for word in words {
+        for (i, ch) in word.chars().enumerate().take(5) {
+            *counts[i].entry(ch).or_insert(0) += 1;
+        }
+    }
+
+    counts.map(|position| {
+        position
+            .into_iter()
+            .map(|(ch, count)| (ch, count as f64 / total))
+            .collect()
+    })
+}
+
+/// Bonus for placing required-but-unplaced (yellow) letters in positions where they are
+/// still allowed and commonly correct. Each such letter contributes the positional
+/// frequency of its best allowed placement in the word.
+pub fn yellow_position_bonus(
+    word: &str,
+    state: &GameState,
+    pos_freq: &[HashMap<char, f64>; 5],
+) -> f64 {
+    let chars: Vec<char> = word.chars().collect();
+    let placed: HashSet<char> = state.greens.iter().flatten().copied().collect();
+
+    state
+        .required_letters
+        .iter()
+        .filter(|letter| !placed.contains(letter))
+        .map(|&letter| {
+            (0..5)
+                .filter(|&i| {
+                    chars.get(i) == Some(&letter)
+                        && state.greens[i].is_none()
+                        && !state.yellows_not_at[i].contains(&letter)
+                })
+                .filter_map(|i| pos_freq[i].get(&letter).copied())
+                .fold(0.0, f64::max)
+        })
+        .sum()
+}
+
+/// How `rank_words_with` scores words. Every strategy ranks higher scores first.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Strategy {
+    /// Letter presence frequency blended with word commonality.
+    #[default]
+    Frequency,
+    /// Expected information (bits) from the feedback the word would produce.
+    Entropy,
+    /// Fraction of candidates guaranteed to be eliminated, i.e. 1 - worst-case bucket share.
+    Minimax,
+    /// The inverse of `Entropy`, for puzzle designers: bits of ambiguity expected to remain
+    /// after the guess, so the least informative candidates rank first.
+    Challenge,
+    /// Word commonality alone, with letter score only breaking ties. Falls back to letter
+    /// score when no commonality data is loaded.
+    Common,
+    /// How close each of the word's letters comes to a 50/50 split of the candidates (see
+    /// `letter_split_score`), so letters every candidate shares count for nothing.
+    Split,
+}
+
+impl Strategy {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Strategy::Frequency => "freq",
+            Strategy::Entropy => "entropy",
+            Strategy::Minimax => "minimax",
+            Strategy::Challenge => "challenge",
+            Strategy::Common => "common",
+            Strategy::Split => "split",
+        }
+    }
+}
+
+impl std::str::FromStr for Strategy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "freq" | "frequency" => Ok(Strategy::Frequency),
+            "entropy" => Ok(Strategy::Entropy),
+            "minimax" => Ok(Strategy::Minimax),
+            "challenge" => Ok(Strategy::Challenge),
+            "common" => Ok(Strategy::Common),
+            "split" => Ok(Strategy::Split),
+            _ => Err(format!(
+                "Unknown strategy '{}' (expected freq, entropy, minimax, challenge, common or split)",
+                s
+            )),
+        }
+    }
+}
+
+/// How much help the binaries show, for players who want to solve mostly on their own.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum HintLevel {
+    /// Level 0: only the candidate count.
+    CountOnly,
+    /// Level 1: the top three suggestions.
+    Nudge,
+    /// Level 2: the usual suggestion list and hints.
+    #[default]
+    Standard,
+    /// Level 3: everything, plus each suggestion's expected remaining candidates.
+    Full,
+}
+
+impl HintLevel {
+    pub const ALL: [HintLevel; 4] = [
+        HintLevel::CountOnly,
+        HintLevel::Nudge,
+        HintLevel::Standard,
+        HintLevel::Full,
+    ];
+
+    pub fn level(self) -> u8 {
+        self as u8
+    }
+
+    /// How many of `limit` suggestions to show at this level.
+    pub fn suggestion_limit(self, limit: usize) -> usize {
+        match self {
+            HintLevel::CountOnly => 0,
+            HintLevel::Nudge => limit.min(3),
+            HintLevel::Standard | HintLevel::Full => limit,
+        }
+    }
+
+    /// Whether to show the extra hints (likely answer, safe guess, endgame verdicts, ...).
+    pub fn shows_analysis(self) -> bool {
+        self >= HintLevel::Standard
+    }
+}
+
+impl std::str::FromStr for HintLevel {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.trim()
+            .parse::<usize>()
+            .ok()
+            .and_then(|n| HintLevel::ALL.get(n).copied())
+            .ok_or_else(|| format!("Unknown hint level '{}' (expected 0 to 3)", s))
+    }
+}
+
+/// Tunable knobs for `rank_words_with`.
+#[derive(Clone, Debug)]
+pub struct RankOptions {
+    pub strategy: Strategy,
+    /// Weight of the yellow-placement bonus (see `yellow_position_bonus`). 0 disables it.
+    pub yellow_bonus_weight: f64,
+    /// Halve the score of words containing a bigram rarer than this share of the
+    /// candidates (e.g. "xylyl"). A heuristic for unlikely answers, not a correctness
+    /// filter: such words stay in the list, just lower. `None` (the default) disables it.
+    pub rare_bigram_threshold: Option<f64>,
+    /// Experimental: down-weight words shaped like past answers. `None` (default) disables it.
+    pub past_answer_bias: Option<PastAnswerBias>,
+    /// Commonality (0-1 scale) at or above which a word counts as common; see `is_common`.
+    pub common_threshold: f64,
+    /// Favor letters in the positions answers use (frequency strategy only). `None`
+    /// (default) disables it.
+    pub answer_positions: Option<AnswerPositionBonus>,
+}
+
+impl Default for RankOptions {
+    fn default() -> Self {
+        Self {
+            strategy: Strategy::default(),
+            yellow_bonus_weight: 0.3,
+            rare_bigram_threshold: None,
+            past_answer_bias: None,
+            common_threshold: DEFAULT_COMMON_THRESHOLD,
+            answer_positions: None,
+        }
+    }
+}
+
+/// Default `RankOptions::common_threshold`: roughly words seen a few thousand times in the
+/// frequency corpus.
+pub const DEFAULT_COMMON_THRESHOLD: f64 = 0.5;
+
+impl RankOptions {
+    /// Whether `word`'s normalized commonality reaches `common_threshold`. Always false
+    /// without commonality data.
+    pub fn is_common(&self, word: &str, commonality: &HashMap<String, f64>) -> bool {
+        commonality
+            .get(word)
+            .is_some_and(|&c| c >= self.common_threshold)
+    }
+}
+
+/// Bitmask of the positions holding a vowel (bit 0 = first letter).
+pub fn vowel_pattern(word: &str) -> u8 {
+    word.chars()
+        .take(5)
+        .enumerate()
+        .filter(|(_, c)| matches!(c, 'a' | 'e' | 'i' | 'o' | 'u'))
+        .fold(0, |mask, (i, _)| mask | (1 << i))
+}
+
+/// Experimental scoring dimension for players who believe past answers influence future
+/// ones: a word's score is reduced by `weight` times the share of past answers with the
+/// same vowel positions. Recent answers count more: each answer's share halves every
+/// `PAST_ANSWER_HALF_LIFE` answers back.
+#[derive(Clone, Debug)]
+pub struct PastAnswerBias {
+    pub weight: f64,
+    pattern_share: HashMap<u8, f64>,
+}
+
+/// Answers back (about a year of daily puzzles) at which a past answer counts half as much
+/// as the latest one in `PastAnswerBias`.
+pub const PAST_ANSWER_HALF_LIFE: f64 = 365.0;
+
+/// Past answer words newest first: by date where the source gives one, otherwise in the
+/// order the source lists them. Undated answers go after dated ones.
+pub fn newest_first(answers: &[PastAnswer]) -> Vec<String> {
+    let mut ordered: Vec<&PastAnswer> = answers.iter().collect();
+    ordered.sort_by_key(|a| std::cmp::Reverse(a.day));
+    ordered.into_iter().map(|a| a.word.clone()).collect()
+}
+
+impl PastAnswerBias {
+    /// `past_answers` must be newest first (see `newest_first`).
+    pub fn new<'a>(past_answers: impl IntoIterator<Item = &'a String>, weight: f64) -> Self {
+        let mut shares: HashMap<u8, f64> = HashMap::new();
+        let mut total = 0.0;
+        for (age, word) in past_answers.into_iter().enumerate() {
+            let decay = 0.5f64.powf(age as f64 / PAST_ANSWER_HALF_LIFE);
+            *shares.entry(vowel_pattern(word)).or_insert(0.0) += decay;
+            total += decay;
+        }
+        let pattern_share = shares
+            .into_iter()
+            .map(|(pattern, share)| (pattern, share / total))
+            .collect();
+        Self {
+            weight,
+            pattern_share,
+        }
+    }
+
+    /// Multiplier applied to a word's score (1.0 = no penalty).
+    pub fn factor(&self, word: &str) -> f64 {
+        let share = self
+            .pattern_share
+            .get(&vowel_pattern(word))
+            .copied()
+            .unwrap_or(0.0);
+        (1.0 - self.weight * share).max(0.0)
+    }
+}
+
+/// Scoring bonus for letters in the positions where actual answers have them. The guess
+/// list's letter distribution differs from the answers' (e.g. a final S is far rarer in
+/// answers), so this tunes suggestions, openers especially, toward real answers. A word
+/// gains `weight` times the mean share of answers with its letter in each position.
+#[derive(Clone, Debug)]
+pub struct AnswerPositionBonus {
+    pub weight: f64,
+    frequency: [HashMap<char, f64>; 5],
+}
+
+impl AnswerPositionBonus {
+    pub fn new<'a>(answers: impl IntoIterator<Item = &'a String>, weight: f64) -> Self {
+        let answers: Vec<&String> = answers.into_iter().collect();
+        Self {
+            weight,
+            frequency: positional_frequency(&answers),
+        }
+    }
+
+    /// Amount added to a word's frequency score.
+    pub fn bonus(&self, word: &str) -> f64 {
+        let total: f64 = word
+            .chars()
+            .zip(&self.frequency)
+            .map(|(ch, position)| position.get(&ch).copied().unwrap_or(0.0))
+            .sum();
+        self.weight * total / 5.0
+    }
+}
+
+/// Number of candidates falling into each feedback pattern (indexed by `feedback_code`)
+/// if `guess` were played.
+pub fn feedback_buckets(guess: &str, candidates: &[&String]) -> [usize; 243] {
+    let mut buckets = [0usize; 243];
+    for answer in candidates {
+        buckets[feedback_code(guess, answer) as usize] += 1;
+    }
+    buckets
+}
+
+/// Expected information in bits from playing `guess` against `candidates`.
+pub fn expected_entropy(guess: &str, candidates: &[&String]) -> f64 {
+    let total = candidates.len() as f64;
+    feedback_buckets(guess, candidates)
+        .iter()
+        .filter(|&&n| n > 0)
+        .map(|&n| {
+            let p = n as f64 / total;
+            -p * p.log2()
+        })
+        .sum()
+}
+
+/// Size of the largest feedback bucket, i.e. candidates left in the worst case.
+pub fn worst_case_bucket(guess: &str, candidates: &[&String]) -> usize {
+    feedback_buckets(guess, candidates)
+        .into_iter()
+        .max()
+        .unwrap_or(0)
+}
+
+/// The feedback an adversary (as in Absurdle) would give for `guess`: the pattern that
+/// keeps the most candidates alive. Ties go to the pattern with the fewest greens and
+/// yellows (the lowest `feedback_code`). `None` when there are no candidates.
+pub fn adversarial_feedback(guess: &str, candidates: &[&String]) -> Option<String> {
+    let buckets = feedback_buckets(guess, candidates);
+    let (code, &size) = buckets
+        .iter()
+        .enumerate()
+        .max_by_key(|&(code, size)| (*size, std::cmp::Reverse(code)))?;
+    (size > 0).then(|| feedback_from_code(code as u8))
+}
+
+/// The feedback patterns `guess` could still get: one per distinct `feedback_for` result
+/// over the words in `words` that satisfy `state`, with how many of them give it. Most
+/// frequent first, ties in `feedback_code` order.
+pub fn possible_feedbacks(
+    state: &GameState,
+    guess: &str,
+    words: &[&String],
+) -> Vec<(String, usize)> {
+    let candidates: Vec<&String> = words.iter().copied().filter(|w| state.matches(w)).collect();
+    feedback_buckets(guess, &candidates)
+        .into_iter()
+        .enumerate()
+        .filter(|&(_, count)| count > 0)
+        .sorted_by_key(|&(code, count)| (std::cmp::Reverse(count), code))
+        .map(|(code, count)| (feedback_from_code(code as u8), count))
+        .collect()
+}
+
+/// One possible result of a guess: a feedback pattern, how many candidates give it (and so
+/// remain afterwards), and its probability with every candidate equally likely.
+#[derive(Clone, Debug, Serialize)]
+pub struct Outcome {
+    pub feedback: String,
+    pub remaining: usize,
+    pub probability: f64,
+}
+
+/// Every feedback `guess` can get against `candidates`, likeliest first (ties in
+/// `feedback_code` order). The "ggggg" outcome, if present, is `guess` being the answer.
+pub fn outcome_distribution(guess: &str, candidates: &[&String]) -> Vec<Outcome> {
+    let total = candidates.len() as f64;
+    possible_feedbacks(&GameState::new(), guess, candidates)
+        .into_iter()
+        .map(|(feedback, remaining)| Outcome {
+            feedback,
+            remaining,
+            probability: remaining as f64 / total,
+        })
+        .collect()
+}
+
+pub fn rank_words<'a>(
     words: &[&'a String],
     commonality: &HashMap<String, f64>,
-) -> Vec<(&'a String, f64)> {
-    let freq = letter_presence_frequency(words);
-    let has_commonality = !commonality.is_empty();
+) -> Vec<(&'a String, f64)> {
+    rank_words_with(
+        words,
+        commonality,
+        &GameState::new(),
+        &RankOptions::default(),
+    )
+}
+
+/// Rank words using the configured strategy and the current game state.
+pub fn rank_words_with<'a>(
+    words: &[&'a String],
+    commonality: &HashMap<String, f64>,
+    state: &GameState,
+    options: &RankOptions,
+) -> Vec<(&'a String, f64)> {
+    let mut scored: Vec<(&String, f64)> = match options.strategy {
+        Strategy::Frequency => frequency_scores(words, commonality, state, options),
+        Strategy::Entropy => words
+            .iter()
+            .map(|w| (*w, expected_entropy(w, words)))
+            .collect(),
+        Strategy::Minimax => {
+            let total = words.len() as f64;
+            words
+                .iter()
+                .map(|w| (*w, 1.0 - worst_case_bucket(w, words) as f64 / total))
+                .collect()
+        }
+        Strategy::Challenge => {
+            // Suggestions are still candidates, so every one could win; the designer
+            // just wants the guesses that give the least away
+            let max_bits = (words.len() as f64).log2();
+            words
+                .iter()
+                .map(|w| (*w, max_bits - expected_entropy(w, words)))
+                .collect()
+        }
+        Strategy::Common if commonality.is_empty() => {
+            frequency_scores(words, commonality, state, options)
+        }
+        Strategy::Common => {
+            // Order by letter score first; the stable sort below then keeps that order
+            // among words of equal commonality
+            let freq = letter_presence_frequency(words);
+            words
+                .iter()
+                .map(|w| (*w, score_word(w, &freq)))
+                .sorted_by(|a, b| b.1.total_cmp(&a.1))
+                .map(|(w, _)| (w, commonality.get(w.as_str()).copied().unwrap_or(0.0)))
+                .collect()
+        }
+        Strategy::Split => {
+            let freq = letter_presence_frequency(words);
+            words
+                .iter()
+                .map(|w| (*w, letter_split_score(w, &freq)))
+                .collect()
+        }
+    };
+    if let Some(bias) = &options.past_answer_bias {
+        for (word, score) in scored.iter_mut() {
+            *score *= bias.factor(word);
+        }
+    }
+    if let Some(threshold) = options.rare_bigram_threshold {
+        let bigrams = bigram_frequency(words);
+        for (word, score) in scored.iter_mut() {
+            if has_rare_bigram(word, &bigrams, threshold) {
+                *score *= 0.5;
+            }
+        }
+    }
+    scored.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+    scored
+}
+
+/// Fraction of `words` that have commonality data (0.0 to 1.0; 0.0 for no words). Words
+/// missing from the frequency list would otherwise all score as the rarest possible.
+pub fn commonality_coverage(words: &[&String], commonality: &HashMap<String, f64>) -> f64 {
+    if words.is_empty() {
+        return 0.0;
+    }
+    let covered = words
+        .iter()
+        .filter(|w| commonality.contains_key(w.as_str()))
+        .count();
+    covered as f64 / words.len() as f64
+}
+
+fn frequency_scores<'a>(
+    words: &[&'a String],
+    commonality: &HashMap<String, f64>,
+    state: &GameState,
+    options: &RankOptions,
+) -> Vec<(&'a String, f64)> {
+    let freq = letter_presence_frequency(words);
+    // Commonality normally gets half the weight; scale that down by its coverage so words
+    // missing from the frequency list aren't over-penalized when many of them are
+    let common_weight = 0.5 * commonality_coverage(words, commonality);
+    let has_commonality = common_weight > 0.0;
+    let use_yellow_bonus = options.yellow_bonus_weight > 0.0
+        && state
+            .required_letters
+            .iter()
+            .any(|l| !state.greens.contains(&Some(*l)));
+    let pos_freq = if use_yellow_bonus {
+        positional_frequency(words)
+    } else {
+        Default::default()
+    };
+
+    words
+        .iter()
+        .map(|w| {
+            let letter_score = score_word(w, &freq);
+            let mut score = if has_commonality {
+                let common_score = commonality.get(w.as_str()).copied().unwrap_or(0.0);
+                (1.0 - common_weight) * letter_score + common_weight * common_score
+            } else {
+                letter_score
+            };
+            if use_yellow_bonus {
+                score += options.yellow_bonus_weight * yellow_position_bonus(w, state, &pos_freq);
+            }
+            if let Some(positions) = &options.answer_positions {
+                score += positions.bonus(w);
+            }
+            (*w, score)
+        })
+        .collect()
+}
+
+/// Fewest all-untested words `fresh_guesses` needs before the binaries use them.
+pub const FRESH_MIN_WORDS: usize = 3;
+
+/// Guesses made only of untested letters, for systematic letter elimination: each word in
+/// `words` with no green, yellow or grey letter, scored by how many of the candidates
+/// contain its letters. Best first; empty when nothing qualifies.
+pub fn fresh_guesses<'a>(
+    words: &[&'a String],
+    candidates: &[&String],
+    state: &GameState,
+) -> Vec<(&'a String, f64)> {
+    let freq = letter_presence_frequency(candidates);
+    words
+        .iter()
+        .filter(|w| w.chars().all(|c| state.is_untested(c)))
+        .map(|w| (*w, score_word(w, &freq)))
+        .sorted_by(|a, b| b.1.total_cmp(&a.1))
+        .collect()
+}
+
+/// The open position (no green) where the candidates disagree most, with the entropy in
+/// bits of its letters across `candidates`. `None` when every open position is settled.
+pub fn uncertain_position(candidates: &[&String], state: &GameState) -> Option<(usize, f64)> {
+    let total = candidates.len() as f64;
+    (0..5)
+        .filter(|&i| state.greens[i].is_none())
+        .map(|i| {
+            let entropy: f64 = candidates
+                .iter()
+                .map(|w| w.as_bytes()[i])
+                .counts()
+                .into_values()
+                .map(|n| {
+                    let p = n as f64 / total;
+                    -p * p.log2()
+                })
+                .sum();
+            (i, entropy)
+        })
+        .filter(|(_, entropy)| *entropy > 0.0)
+        .max_by(|a, b| a.1.total_cmp(&b.1).then(b.0.cmp(&a.0)))
+}
+
+/// Guesses from `words` ranked by how much their feedback would reveal about the letter
+/// at `position` (0-based) among `candidates`: the bits of that slot's entropy the
+/// feedback is expected to remove. Best first; words that reveal nothing are left out.
+pub fn focus_position_guesses<'a>(
+    words: &[&'a String],
+    candidates: &[&String],
+    position: usize,
+) -> Vec<(&'a String, f64)> {
+    let total = candidates.len() as f64;
+    let slot_letters: Vec<u8> = candidates.iter().map(|w| w.as_bytes()[position]).collect();
+    let slot_entropy: f64 = slot_letters
+        .iter()
+        .counts()
+        .into_values()
+        .map(|n| {
+            let p = n as f64 / total;
+            -p * p.log2()
+        })
+        .sum();
+    words
+        .iter()
+        .map(|guess| {
+            let mut buckets = [0usize; 243];
+            let mut joint: HashMap<(u8, u8), usize> = HashMap::new();
+            for (answer, &letter) in candidates.iter().zip(&slot_letters) {
+                let code = feedback_code(guess, answer);
+                buckets[code as usize] += 1;
+                *joint.entry((code, letter)).or_default() += 1;
+            }
+            // Entropy of the slot's letter left once the feedback is known
+            let remaining: f64 = joint
+                .iter()
+                .map(|(&(code, _), &n)| {
+                    let p = n as f64 / buckets[code as usize] as f64;
+                    -(n as f64 / total) * p.log2()
+                })
+                .sum();
+            (*guess, slot_entropy - remaining)
+        })
+        .filter(|(_, gain)| *gain > 1e-9)
+        .sorted_by(|a, b| b.1.total_cmp(&a.1))
+        .collect()
+}
+
+/// Move `words` to the end of a ranked list, keeping the ranked order on each side. Used to
+/// de-prioritize past answers without removing them.
+pub fn demote_words<W: AsRef<str>>(ranked: &mut [(W, f64)], words: &HashSet<String>) {
+    if words.is_empty() {
+        return;
+    }
+    ranked.sort_by_key(|(word, _)| words.contains(word.as_ref()));
+}
+
+/// Move favorite words to the front of a ranked list, keeping the ranked order within
+/// favorites and non-favorites. Only words already in the list (i.e. still valid) move.
+pub fn pin_favorites<W: AsRef<str>>(ranked: &mut [(W, f64)], favorites: &HashSet<String>) {
+    if favorites.is_empty() {
+        return;
+    }
+    ranked.sort_by_key(|(word, _)| !favorites.contains(word.as_ref()));
+}
+
+/// Key a word by its sorted letters, so anagrams ("spare", "pears", "parse") share a key.
+pub fn letter_signature(word: &str) -> String {
+    word.chars().sorted().collect()
+}
+
+/// Collapse anagrams in a ranked list to their top-scoring word, returning each kept word
+/// with the number of lower-ranked anagrams folded into it.
+pub fn group_anagrams<W: AsRef<str>>(ranked: Vec<(W, f64)>) -> Vec<(W, f64, usize)> {
+    let mut grouped: Vec<(W, f64, usize)> = Vec::new();
+    let mut index: HashMap<String, usize> = HashMap::new();
+    for (word, score) in ranked {
+        let key = letter_signature(word.as_ref());
+        match index.get(&key) {
+            Some(&i) => grouped[i].2 += 1,
+            None => {
+                index.insert(key, grouped.len());
+                grouped.push((word, score, 0));
+            }
+        }
+    }
+    grouped
+}
+
+/// Whether `word` makes a human-guessable opener: it has at least one of a, e, i, o, u
+/// and, if `max_consonant_run` is set, no longer run of consecutive consonants.
+pub fn is_human_opener(word: &str, max_consonant_run: Option<usize>) -> bool {
+    let is_vowel = |c: char| matches!(c, 'a' | 'e' | 'i' | 'o' | 'u');
+    if !word.chars().any(is_vowel) {
+        return false;
+    }
+    let Some(max_run) = max_consonant_run else {
+        return true;
+    };
+    let mut run = 0;
+    for c in word.chars() {
+        run = if is_vowel(c) { 0 } else { run + 1 };
+        if run > max_run {
+            return false;
+        }
+    }
+    true
+}
+
+/// Greedily pick up to `limit` words from a ranked list, in rank order, skipping any word
+/// that has fewer than `min_difference` distinct letters absent from an already-picked
+/// word. Keeps near-duplicates ("shale", "share", "shade") from crowding the top picks.
+pub fn diverse_selection<W: AsRef<str>>(
+    ranked: Vec<(W, f64)>,
+    limit: usize,
+    min_difference: usize,
+) -> Vec<(W, f64)> {
+    let mut picked: Vec<(W, f64)> = Vec::new();
+    let mut picked_letters: Vec<HashSet<char>> = Vec::new();
+    for (word, score) in ranked {
+        if picked.len() >= limit {
+            break;
+        }
+        let letters: HashSet<char> = word.as_ref().chars().collect();
+        let distinct = picked_letters
+            .iter()
+            .all(|other| letters.difference(other).count() >= min_difference);
+        if distinct {
+            picked_letters.push(letters);
+            picked.push((word, score));
+        }
+    }
+    picked
+}
+
+/// `score` as a percentage of the turn's `top` score, so the best suggestion reads 100.
+pub fn relative_score(score: f64, top: f64) -> f64 {
+    if top > 0.0 {
+        100.0 * score / top
+    } else {
+        0.0
+    }
+}
+
+/// Whether `score` reaches `floor` percent of the best score `top`. Everything passes
+/// when the best score isn't positive, since nothing can be compared against it.
+pub fn meets_score_floor(score: f64, top: f64, floor: f64) -> bool {
+    top <= 0.0 || relative_score(score, top) >= floor
+}
+
+/// Shuffle each run of equal-score words in a ranked list, leaving the score order intact.
+/// The same `seed` always gives the same order, so practice games stay reproducible.
+pub fn shuffle_ties<W>(ranked: &mut [(W, f64)], seed: u64) {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut start = 0;
+    while start < ranked.len() {
+        let score = ranked[start].1;
+        let end = start
+            + ranked[start..]
+                .iter()
+                .take_while(|(_, s)| *s == score)
+                .count();
+        ranked[start..end].shuffle(&mut rng);
+        start = end;
+    }
+}
+
+pub fn rank_words_owned(
+    words: &[String],
+    commonality: &HashMap<String, f64>,
+) -> Vec<(String, f64)> {
+    rank_words_owned_with(
+        words,
+        commonality,
+        &GameState::new(),
+        &RankOptions::default(),
+    )
+}
+
+pub fn rank_words_owned_with(
+    words: &[String],
+    commonality: &HashMap<String, f64>,
+    state: &GameState,
+    options: &RankOptions,
+) -> Vec<(String, f64)> {
+    let word_refs: Vec<&String> = words.iter().collect();
+    rank_words_with(&word_refs, commonality, state, options)
+        .into_iter()
+        .map(|(w, score)| (w.clone(), score))
+        .collect()
+}
+
+// ---------- Endgame search ----------
+
+/// Guesses allowed in a standard game.
+pub const MAX_GUESSES: usize = 6;
+
+/// Scale and exponent of `estimated_guesses`' curve.
+///
+/// Calibration: least-squares fit of `1 + a * ln(n)^b` to self-play, pairing each step's
+/// candidate count with the guesses it still took. Data: `solve --jsonl` (default
+/// strategy) over every word of a 2,701-word list, 9,778 steps, averaging 3.62 guesses.
+/// The official answer list couldn't be fetched for that run, so re-fit against it (same
+/// command, `WORDLE_WORD_LIST` unset) when the defaults drift.
+const ESTIMATE_SCALE: f64 = 0.74;
+const ESTIMATE_EXPONENT: f64 = 0.61;
+
+/// Rough number of guesses still needed, including the winning one, with `candidate_count`
+/// candidates left. Depends only on the count, so it applies to any constraint state: 1.0
+/// for one candidate, about 1.6 for two, 2.2 for ten and 2.9 for a hundred. 0.0 when
+/// nothing is left.
+pub fn estimated_guesses(candidate_count: usize) -> f64 {
+    match candidate_count {
+        0 => 0.0,
+        n => 1.0 + ESTIMATE_SCALE * (n as f64).ln().powf(ESTIMATE_EXPONENT),
+    }
+}
+
+/// Largest candidate set the binaries run `guaranteed_solution` on.
+pub const ENDGAME_SEARCH_LIMIT: usize = 50;
 
-    let mut scored: Vec<(&String, f64)> = words
+/// Average number of candidates left after playing `guess` against `candidates`.
+pub fn expected_remaining(guess: &str, candidates: &[&String]) -> f64 {
+    let total = candidates.len().max(1) as f64;
+    bucket_expectation(&feedback_buckets(guess, candidates), total)
+}
+
+fn bucket_expectation(buckets: &[usize; 243], total: f64) -> f64 {
+    buckets.iter().map(|&b| (b * b) as f64).sum::<f64>() / total
+}
+
+/// Side-by-side figures for judging one guess against the current candidates.
+#[derive(Clone, Debug, Serialize)]
+pub struct GuessComparison {
+    pub word: String,
+    /// Average number of candidates left after this guess.
+    pub expected_remaining: f64,
+    /// Candidates left in the worst case.
+    pub worst_case: usize,
+    /// Whether the guess could itself be the answer.
+    pub is_candidate: bool,
+    pub commonality: f64,
+}
+
+pub fn compare_guess(
+    guess: &str,
+    candidates: &[&String],
+    commonality: &HashMap<String, f64>,
+) -> GuessComparison {
+    let buckets = feedback_buckets(guess, candidates);
+    let total = candidates.len().max(1) as f64;
+    GuessComparison {
+        word: guess.to_string(),
+        expected_remaining: bucket_expectation(&buckets, total),
+        worst_case: buckets.into_iter().max().unwrap_or(0),
+        is_candidate: candidates.iter().any(|c| c.as_str() == guess),
+        commonality: commonality.get(guess).copied().unwrap_or(0.0),
+    }
+}
+
+/// Share of candidates below which a letter counts as rare in `candidate_reasons`.
+pub const RARE_LETTER_SHARE: f64 = 0.1;
+
+/// Why a candidate stands out within the remaining set.
+#[derive(Clone, Debug, Serialize)]
+pub struct CandidateReasons {
+    pub word: String,
+    /// Letters no other candidate has.
+    pub unique_letters: Vec<char>,
+    /// Letters found in fewer than `RARE_LETTER_SHARE` of the candidates (including unique ones).
+    pub rare_letters: Vec<char>,
+}
+
+/// Each candidate with the letters that set it apart from the rest of the set.
+pub fn candidate_reasons(candidates: &[&String]) -> Vec<CandidateReasons> {
+    let presence = letter_presence_frequency(candidates);
+    let total = candidates.len() as f64;
+    candidates
         .iter()
-        .map(|w| {
-            let letter_score = score_word(w, &freq);
-            if has_commonality {
-                let common_score = commonality.get(w.as_str()).copied().unwrap_or(0.0);
-                (*w, 0.5 * letter_score + 0.5 * common_score)
-            } else {
-                (*w, letter_score)
+        .map(|word| {
+            let letters: Vec<(char, f64)> = word
+                .chars()
+                .unique()
+                .sorted()
+                .map(|c| (c, presence.get(&c).copied().unwrap_or(0.0)))
+                .collect();
+            CandidateReasons {
+                word: word.to_string(),
+                unique_letters: letters
+                    .iter()
+                    .filter(|(_, share)| (share * total).round() as usize == 1)
+                    .map(|(c, _)| *c)
+                    .collect(),
+                rare_letters: letters
+                    .iter()
+                    .filter(|(_, share)| *share < RARE_LETTER_SHARE)
+                    .map(|(c, _)| *c)
+                    .collect(),
             }
         })
+        .collect()
+}
+
+/// Candidates that put an unplaced yellow letter in one position.
+#[derive(Clone, Debug, Serialize)]
+pub struct YellowPlacement {
+    pub letter: char,
+    /// 1-based position.
+    pub position: usize,
+    pub words: Vec<String>,
+}
+
+/// Group `candidates` by where they put each required letter that has no green yet, e.g.
+/// five with the R in position 3 and three with it in position 5. Sorted by letter, then
+/// largest group first. A word with the letter twice appears in both groups.
+pub fn yellow_placements(candidates: &[&String], state: &GameState) -> Vec<YellowPlacement> {
+    state
+        .required_letters
+        .iter()
+        .filter(|l| !state.greens.contains(&Some(**l)))
+        .sorted()
+        .flat_map(|&letter| {
+            (0..5)
+                .filter(|&i| state.greens[i].is_none())
+                .map(move |i| YellowPlacement {
+                    letter,
+                    position: i + 1,
+                    words: candidates
+                        .iter()
+                        .filter(|w| w.chars().nth(i) == Some(letter))
+                        .map(|w| w.to_string())
+                        .collect(),
+                })
+                .filter(|group| !group.words.is_empty())
+                .sorted_by_key(|group| std::cmp::Reverse(group.words.len()))
+        })
+        .collect()
+}
+
+/// Largest candidate set for which the binaries name the most likely answer.
+pub const LIKELY_ANSWER_LIMIT: usize = 10;
+
+/// Each candidate's share of the candidates' total commonality, as a rough probability of
+/// being the answer, most likely first. `None` without usable commonality data.
+pub fn answer_probabilities<'a>(
+    candidates: &[&'a String],
+    commonality: &HashMap<String, f64>,
+) -> Option<Vec<(&'a String, f64)>> {
+    let weights: Vec<(&String, f64)> = candidates
+        .iter()
+        .map(|&w| {
+            (
+                w,
+                commonality.get(w.as_str()).copied().unwrap_or(0.0).max(0.0),
+            )
+        })
         .collect();
-    scored.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
-    scored
+    let total: f64 = weights.iter().map(|(_, c)| c).sum();
+    if total <= 0.0 {
+        return None;
+    }
+    Some(
+        weights
+            .into_iter()
+            .map(|(w, c)| (w, c / total))
+            .sorted_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal))
+            .collect(),
+    )
 }
 
-pub fn rank_words_owned(
-    words: &[String],
+/// The single candidate most likely to be the answer: the most common one, or without
+/// commonality data the best safe guess. Used once the guesses run out.
+pub fn most_likely_answer<'a>(
+    candidates: &[&'a String],
     commonality: &HashMap<String, f64>,
-) -> Vec<(String, f64)> {
-    let word_refs: Vec<&String> = words.iter().collect();
-    let freq = letter_presence_frequency(&word_refs);
-    let has_commonality = !commonality.is_empty();
+) -> Option<&'a String> {
+    answer_probabilities(candidates, commonality)
+        .map(|probs| probs[0].0)
+        .or_else(|| best_candidate_guess(candidates, commonality))
+}
 
-    let mut scored: Vec<(String, f64)> = words
+/// A guess whose feedback differs between answers `a` and `b`, so playing it tells them
+/// apart. Either candidate qualifies (one gets all greens, the other doesn't) and is
+/// preferred since it could also win, so `a` is tried first, then `b`, then `guess_pool`.
+pub fn disambiguating_guess(a: &str, b: &str, guess_pool: &[&String]) -> Option<String> {
+    let candidates = [a, b];
+    candidates
+        .into_iter()
+        .chain(guess_pool.iter().map(|w| w.as_str()))
+        .find(|w| feedback_code(w, a) != feedback_code(w, b))
+        .map(str::to_string)
+}
+
+/// Whether three or more candidates remain and all are anagrams of one another ("least",
+/// "slate", "stale", ...). Guessing them one at a time only moves the same letters around,
+/// so an outside probe usually settles it faster. Two candidates are left to
+/// `disambiguating_guess`.
+pub fn all_anagrams(candidates: &[&String]) -> bool {
+    candidates.len() >= 3 && candidates.iter().map(|w| letter_signature(w)).all_equal()
+}
+
+/// The guess from `guess_pool` that best splits an anagram cluster: the smallest
+/// worst-case group, then the fewest candidates left on average, then a candidate (which
+/// could win outright), then pool order. Returns the worst-case group size with it.
+pub fn anagram_probe<'a>(
+    candidates: &[&String],
+    guess_pool: &[&'a String],
+) -> Option<(&'a String, usize)> {
+    guess_pool
         .iter()
-        .map(|w| {
-            let letter_score = score_word(w, &freq);
-            if has_commonality {
-                let common_score = commonality.get(w.as_str()).copied().unwrap_or(0.0);
-                (w.clone(), 0.5 * letter_score + 0.5 * common_score)
+        .map(|&word| {
+            let buckets = feedback_buckets(word, candidates);
+            let worst = buckets.iter().copied().max().unwrap_or(0);
+            let spread: usize = buckets.iter().map(|b| b * b).sum();
+            (word, worst, spread, !candidates.contains(&word))
+        })
+        .min_by_key(|&(_, worst, spread, outside)| (worst, spread, outside))
+        .map(|(word, worst, _, _)| (word, worst))
+}
+
+/// Largest candidate set the binaries compute `best_candidate_guess` for (it compares
+/// every candidate against every other).
+pub const SAFE_GUESS_LIMIT: usize = 500;
+
+/// The best "safe" guess: the candidate (so it could win outright) that leaves the fewest
+/// candidates on average, i.e. minimizes the sum of squared feedback bucket sizes. Ties
+/// go to the more common word.
+pub fn best_candidate_guess<'a>(
+    candidates: &[&'a String],
+    commonality: &HashMap<String, f64>,
+) -> Option<&'a String> {
+    best_guess_among(candidates, candidates, commonality)
+}
+
+/// Most top-ranked words `best_opener` tries.
+pub const OPENER_SHORTLIST: usize = 200;
+
+/// The opener that leaves the fewest of `candidates` on average, like
+/// `best_candidate_guess` but tried only among the `OPENER_SHORTLIST` best by the default
+/// ranking, so a full word list costs a few hundred passes rather than one per word.
+pub fn best_opener<'a>(
+    candidates: &[&'a String],
+    commonality: &HashMap<String, f64>,
+) -> Option<&'a String> {
+    let shortlist: Vec<&String> = rank_words(candidates, commonality)
+        .into_iter()
+        .take(OPENER_SHORTLIST)
+        .map(|(word, _)| word)
+        .collect();
+    best_guess_among(&shortlist, candidates, commonality)
+}
+
+/// The word in `guesses` minimizing the sum of squared feedback bucket sizes over
+/// `candidates`, ties going to the more common word.
+fn best_guess_among<'a>(
+    guesses: &[&'a String],
+    candidates: &[&String],
+    commonality: &HashMap<String, f64>,
+) -> Option<&'a String> {
+    guesses
+        .iter()
+        .map(|&word| {
+            let expected: usize = feedback_buckets(word, candidates)
+                .iter()
+                .map(|b| b * b)
+                .sum();
+            let common = commonality.get(word.as_str()).copied().unwrap_or(0.0);
+            (word, expected, common)
+        })
+        .min_by(|a, b| {
+            a.1.cmp(&b.1)
+                .then(b.2.partial_cmp(&a.2).unwrap_or(std::cmp::Ordering::Equal))
+        })
+        .map(|(word, _, _)| word)
+}
+
+/// The trickiest possible answer: the candidate that stays most ambiguous even after the
+/// guess that best isolates it, with how many candidates that guess would still leave
+/// (itself included). The other candidates serve as probes, since guessing the answer
+/// itself trivially pins it down. Ties go to the earlier candidate; `None` with fewer
+/// than two candidates.
+pub fn hardest_candidate<'a>(candidates: &[&'a String]) -> Option<(&'a String, usize)> {
+    if candidates.len() < 2 {
+        return None;
+    }
+    let mut best_left = vec![usize::MAX; candidates.len()];
+    for (i, probe) in candidates.iter().enumerate() {
+        let buckets = feedback_buckets(probe, candidates);
+        for (j, answer) in candidates.iter().enumerate() {
+            if i != j {
+                best_left[j] = best_left[j].min(buckets[feedback_code(probe, answer) as usize]);
+            }
+        }
+    }
+    candidates
+        .iter()
+        .zip(best_left)
+        .rev()
+        .max_by_key(|(_, left)| *left)
+        .map(|(&word, left)| (word, left))
+}
+
+/// A guess that guarantees a win within `guesses_left` guesses whatever the answer, found
+/// by exhaustive minimax search over the candidates (which also serve as the guess pool).
+/// Returns `None` if no such guess exists. Exponential, so only call it on small sets.
+pub fn guaranteed_solution(candidates: &[&String], guesses_left: usize) -> Option<String> {
+    if guesses_left == 0 || candidates.is_empty() {
+        return None;
+    }
+    if candidates.len() == 1 {
+        return Some(candidates[0].clone());
+    }
+    if guesses_left == 1 {
+        return None;
+    }
+
+    candidates
+        .iter()
+        .find(|guess| {
+            let mut buckets: HashMap<u8, Vec<&String>> = HashMap::new();
+            for answer in candidates {
+                buckets
+                    .entry(feedback_code(guess, answer))
+                    .or_default()
+                    .push(answer);
+            }
+            buckets
+                .iter()
+                .filter(|(code, _)| **code != 242)
+                .all(|(_, bucket)| guaranteed_solution(bucket, guesses_left - 1).is_some())
+        })
+        .map(|guess| guess.to_string())
+}
+
+// ---------- Multi-board ----------
+
+/// A Dordle/Quordle-style game: several boards, each with its own answer, that every
+/// guess is played on at once.
+#[derive(Clone)]
+pub struct MultiBoard {
+    pub boards: Vec<GameState>,
+    pub solved: Vec<bool>,
+    pub guesses: Vec<String>,
+}
+
+impl MultiBoard {
+    pub fn new(count: usize) -> Self {
+        Self {
+            boards: vec![GameState::new(); count],
+            solved: vec![false; count],
+            guesses: Vec::new(),
+        }
+    }
+
+    /// Indices of the boards still being played.
+    pub fn unsolved(&self) -> Vec<usize> {
+        (0..self.boards.len())
+            .filter(|&i| !self.solved[i])
+            .collect()
+    }
+
+    pub fn is_solved(&self) -> bool {
+        self.solved.iter().all(|&s| s)
+    }
+
+    /// Play `guess` with one feedback per unsolved board, in board order. A board whose
+    /// feedback is all green is marked solved.
+    pub fn update(&mut self, guess: &str, feedbacks: &[String]) -> Result<(), String> {
+        let unsolved = self.unsolved();
+        if feedbacks.len() != unsolved.len() {
+            return Err(format!(
+                "Expected feedback for {} board{}, got {}",
+                unsolved.len(),
+                if unsolved.len() == 1 { "" } else { "s" },
+                feedbacks.len()
+            ));
+        }
+        for (&i, feedback) in unsolved.iter().zip(feedbacks) {
+            if feedback == "ggggg" {
+                self.solved[i] = true;
+            }
+            self.boards[i].update(guess, feedback);
+        }
+        self.guesses.push(guess.to_string());
+        Ok(())
+    }
+}
+
+/// Largest guess-pool size times total candidates for which `multi_board_guesses` scores
+/// by entropy; above it, letter frequency keeps the first guesses fast.
+pub const MULTI_BOARD_ENTROPY_LIMIT: usize = 2_000_000;
+
+/// Guesses ranked by combined value across the unsolved boards, given each board's
+/// candidates. The pool is every candidate on any board. A word scores the information it
+/// would give on each board with more than one candidate (expected entropy in bits, or
+/// its letter score relative to that board's best while the pool is large) plus its
+/// chance of solving each board outright, so a board down to one word gets it played.
+pub fn multi_board_guesses<'a>(boards: &[Vec<&'a String>]) -> Vec<(&'a String, f64)> {
+    let pool: Vec<&String> = boards.iter().flatten().copied().sorted().dedup().collect();
+    let open: Vec<&Vec<&String>> = boards.iter().filter(|b| b.len() > 1).collect();
+    let total: usize = open.iter().map(|b| b.len()).sum();
+    let use_entropy = pool.len() * total <= MULTI_BOARD_ENTROPY_LIMIT;
+    let letter_scores: Vec<(HashMap<char, f64>, f64)> = if use_entropy {
+        Vec::new()
+    } else {
+        open.iter()
+            .map(|board| {
+                let freq = letter_presence_frequency(board);
+                let top = pool
+                    .iter()
+                    .map(|w| score_word(w, &freq))
+                    .fold(0.0, f64::max);
+                (freq, top)
+            })
+            .collect()
+    };
+
+    pool.iter()
+        .map(|word| {
+            let information: f64 = if use_entropy {
+                open.iter().map(|board| expected_entropy(word, board)).sum()
             } else {
-                (w.clone(), letter_score)
+                letter_scores
+                    .iter()
+                    .map(|(freq, top)| relative_score(score_word(word, freq), *top) / 100.0)
+                    .sum()
+            };
+            let solve_chance: f64 = boards
+                .iter()
+                .filter(|board| board.contains(word))
+                .map(|board| 1.0 / board.len() as f64)
+                .sum();
+            (*word, information + solve_chance)
+        })
+        .sorted_by(|a, b| b.1.total_cmp(&a.1))
+        .collect()
+}
+
+// ---------- Self-play ----------
+
+/// One guess made by `auto_solve`.
+pub struct SolveStep {
+    pub guess: String,
+    pub score: f64,
+    pub feedback: String,
+    pub candidates_before: usize,
+    pub candidates_after: usize,
+}
+
+/// Play a game against a known answer, always guessing the top-ranked candidate
+/// (or `opener` for the first guess, if given).
+/// Stops when solved, when no candidates remain, or after `max_guesses` guesses.
+pub fn auto_solve(
+    answer: &str,
+    candidates: &[&String],
+    commonality: &HashMap<String, f64>,
+    options: &RankOptions,
+    opener: Option<&str>,
+    max_guesses: usize,
+) -> Vec<SolveStep> {
+    let mut state = GameState::new();
+    let mut remaining: Vec<&String> = candidates.to_vec();
+    let mut steps = Vec::new();
+
+    while steps.len() < max_guesses {
+        let ranked = rank_words_with(&remaining, commonality, &state, options);
+        let (guess, score) = match opener.filter(|_| steps.is_empty()) {
+            Some(word) => {
+                let score = ranked
+                    .iter()
+                    .find(|(w, _)| w.as_str() == word)
+                    .map_or(0.0, |(_, s)| *s);
+                (word.to_string(), score)
             }
+            None => match ranked.first() {
+                Some((w, s)) => (w.to_string(), *s),
+                None => break,
+            },
+        };
+        let feedback = feedback_for(&guess, answer);
+        let candidates_before = remaining.len();
+
+        state.update(&guess, &feedback);
+        remaining.retain(|w| state.matches(w));
+
+        let solved = feedback == "ggggg";
+        steps.push(SolveStep {
+            guess,
+            score,
+            feedback,
+            candidates_before,
+            candidates_after: remaining.len(),
+        });
+        if solved {
+            break;
+        }
+    }
+
+    steps
+}
+
+/// The best second guess after `opener` for each feedback it can get against
+/// `candidates`, as a lookup table to memorize (like the well-known "salet" tree).
+/// Each entry is the top-ranked word among the candidates left by that feedback;
+/// feedback no candidate produces, and the winning `ggggg`, are left out.
+pub fn opener_tree(
+    opener: &str,
+    candidates: &[&String],
+    commonality: &HashMap<String, f64>,
+    options: &RankOptions,
+) -> HashMap<String, String> {
+    let mut buckets: HashMap<String, Vec<&String>> = HashMap::new();
+    for &answer in candidates {
+        buckets
+            .entry(feedback_for(opener, answer))
+            .or_default()
+            .push(answer);
+    }
+    buckets.remove("ggggg");
+
+    buckets
+        .into_iter()
+        .filter_map(|(feedback, remaining)| {
+            let mut state = GameState::new();
+            state.update(opener, &feedback);
+            let (best, _) = *rank_words_with(&remaining, commonality, &state, options).first()?;
+            Some((feedback, best.clone()))
         })
+        .collect()
+}
+
+// ---------- One-call API ----------
+
+/// Options for `suggest`.
+#[derive(Clone, Debug)]
+pub struct SuggestOptions {
+    /// Strategy and scoring weights.
+    pub rank: RankOptions,
+    /// Most suggestions to return.
+    pub limit: usize,
+}
+
+impl Default for SuggestOptions {
+    fn default() -> Self {
+        Self {
+            rank: RankOptions::default(),
+            limit: 15,
+        }
+    }
+}
+
+/// Ranked suggestions and the constraints behind them, from `suggest`.
+#[derive(Clone, Debug, Serialize)]
+pub struct SuggestResult {
+    /// Best first, with their scores.
+    pub suggestions: Vec<(String, f64)>,
+    pub candidate_count: usize,
+    /// Greens as a pattern, e.g. `_R_N_`.
+    pub green: String,
+    pub required: String,
+    pub excluded: String,
+}
+
+/// Replay `history` (guess, feedback) against `words`, filter the candidates and rank
+/// them. The pure core of `suggest`, for callers that load their own word data.
+pub fn suggest_with(
+    history: &[(String, String)],
+    opts: &SuggestOptions,
+    words: &[String],
+    commonality: &HashMap<String, f64>,
+) -> Result<SuggestResult, String> {
+    let mut state = GameState::new();
+    for (guess, feedback) in history {
+        if !is_valid_word(guess) || !is_valid_feedback(feedback) {
+            return Err(format!("Invalid guess or feedback: {} {}", guess, feedback));
+        }
+        state.update(guess, feedback);
+    }
+
+    let candidates: Vec<&String> = words
+        .iter()
+        .filter(|w| state.matches(w) && !state.guessed.contains(*w))
         .collect();
-    scored.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
-    scored
+    let suggestions = rank_words_with(&candidates, commonality, &state, &opts.rank)
+        .into_iter()
+        .take(opts.limit)
+        .map(|(w, score)| (w.clone(), score))
+        .collect();
+
+    Ok(SuggestResult {
+        suggestions,
+        candidate_count: candidates.len(),
+        green: state.green_display(),
+        required: state.required_display(),
+        excluded: state.excluded_display(),
+    })
+}
+
+/// Candidates (valid words minus past answers and regular plurals) and their
+/// commonality, loaded once per process for `suggest`.
+#[cfg(feature = "fetch")]
+struct SuggestData {
+    words: Vec<String>,
+    commonality: HashMap<String, f64>,
+}
+
+/// Suggestions for a game given its history, in one call: fetches the word data on first
+/// use (cached for the rest of the process once a load succeeds), replays the guesses,
+/// filters and ranks. A failed load is returned as an error and retried on the next call.
+#[cfg(feature = "fetch")]
+pub fn suggest(
+    history: &[(String, String)],
+    opts: SuggestOptions,
+) -> Result<SuggestResult, String> {
+    static DATA: std::sync::Mutex<Option<SuggestData>> = std::sync::Mutex::new(None);
+    let mut data = DATA.lock().unwrap_or_else(|e| e.into_inner());
+    if data.is_none() {
+        let all = all_words()?;
+        let used = used_words();
+        let available: HashSet<&String> = all.difference(&used).collect();
+        let freq_data = load_frequency_data(&frequency_sources(), &available);
+        let mut words: Vec<String> = available.into_iter().cloned().sorted().collect();
+        filter_regular_plurals(&mut words, &freq_data.dictionary);
+        *data = Some(SuggestData {
+            words,
+            commonality: freq_data.commonality,
+        });
+    }
+    let data = data.as_ref().expect("suggest data loaded above");
+    suggest_with(history, &opts, &data.words, &data.commonality)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::{prop_assert, prop_assert_eq, proptest};
+    use proptest::strategy::Strategy as _;
+
+    /// Five letters from a small alphabet, so repeated letters come up often.
+    fn word() -> impl proptest::strategy::Strategy<Value = String> {
+        proptest::collection::vec(proptest::char::range('a', 'f'), 5)
+            .prop_map(|chars| chars.into_iter().collect())
+    }
+
+    fn words(list: &[&str]) -> Vec<String> {
+        list.iter().map(|w| w.to_string()).collect()
+    }
+
+    #[test]
+    fn anagram_cluster_gets_a_probe() {
+        let cluster = words(&["least", "slate", "stale", "steal", "tales"]);
+        let candidates: Vec<&String> = cluster.iter().collect();
+        assert!(all_anagrams(&candidates));
+
+        let pool = words(&[
+            "least", "slate", "stale", "steal", "tales", "crane", "plait",
+        ]);
+        let guess_pool: Vec<&String> = pool.iter().collect();
+        let (probe, worst) = anagram_probe(&candidates, &guess_pool).unwrap();
+        assert!(worst < candidates.len());
+        assert_eq!(worst, worst_case_bucket(probe, &candidates));
+    }
+
+    #[test]
+    fn mixed_letters_are_not_an_anagram_cluster() {
+        let mixed = words(&["least", "slate", "crane"]);
+        let candidates: Vec<&String> = mixed.iter().collect();
+        assert!(!all_anagrams(&candidates));
+        // Two anagrams are left to disambiguating_guess
+        assert!(!all_anagrams(&candidates[..2]));
+    }
+
+    #[cfg(feature = "fetch")]
+    #[test]
+    fn transient_errors_are_retried() {
+        let mut server = mockito::Server::new();
+        let failing = server
+            .mock("GET", "/words")
+            .with_status(503)
+            .expect(2)
+            .create();
+        let working = server
+            .mock("GET", "/words")
+            .with_body("crane\n")
+            .expect(1)
+            .create();
+
+        let url = format!("{}/words", server.url());
+        let client = reqwest::blocking::Client::new();
+        let response = send_with_retry(&client, |client| client.get(&url), 2).unwrap();
+        assert_eq!(response.status(), reqwest::StatusCode::OK);
+        assert_eq!(response.text().unwrap(), "crane\n");
+        failing.assert();
+        working.assert();
+    }
+
+    #[cfg(feature = "fetch")]
+    #[test]
+    fn timeouts_are_retried() {
+        use std::io::{Read, Write};
+
+        // The first connection never gets an answer; the second one does
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/words", listener.local_addr().unwrap());
+        std::thread::spawn(move || {
+            let (_stalled, _) = listener.accept().unwrap();
+            let (mut stream, _) = listener.accept().unwrap();
+            stream.read_exact(&mut [0; 16]).unwrap();
+            stream
+                .write_all(
+                    b"HTTP/1.1 200 OK\r\nContent-Length: 6\r\nConnection: close\r\n\r\ncrane\n",
+                )
+                .unwrap();
+            std::thread::sleep(std::time::Duration::from_secs(1));
+        });
+
+        let client = reqwest::blocking::Client::builder()
+            .timeout(std::time::Duration::from_millis(200))
+            .build()
+            .unwrap();
+        let response = send_with_retry(&client, |client| client.get(&url), 1).unwrap();
+        assert_eq!(response.text().unwrap(), "crane\n");
+    }
+
+    #[cfg(feature = "fetch")]
+    #[test]
+    fn client_errors_are_not_retried() {
+        let mut server = mockito::Server::new();
+        let missing = server
+            .mock("GET", "/words")
+            .with_status(404)
+            .expect(1)
+            .create();
+
+        let url = format!("{}/words", server.url());
+        let client = reqwest::blocking::Client::new();
+        let response = send_with_retry(&client, |client| client.get(&url), 2).unwrap();
+        assert_eq!(response.status(), reqwest::StatusCode::NOT_FOUND);
+        missing.assert();
+    }
+
+    #[test]
+    fn past_answers_are_ordered_newest_first() {
+        let answer = |word: &str, day| PastAnswer {
+            word: word.to_string(),
+            day,
+        };
+        let answers = [
+            answer("fight", None),
+            answer("crane", Some(10)),
+            answer("slate", Some(20)),
+            answer("light", None),
+        ];
+        assert_eq!(
+            newest_first(&answers),
+            words(&["slate", "crane", "fight", "light"])
+        );
+    }
+
+    #[test]
+    fn recent_past_answers_weigh_more() {
+        // "crane" and "slate" share a vowel pattern, "fight" has another
+        let older = PastAnswerBias::new(&words(&["fight", "crane", "slate"]), 1.0);
+        let newer = PastAnswerBias::new(&words(&["crane", "slate", "fight"]), 1.0);
+        assert!(newer.factor("trace") < older.factor("trace"));
+        assert!(newer.factor("night") > older.factor("night"));
+        // Shares still add up to the whole list
+        let flat = PastAnswerBias::new(&words(&["crane"]), 0.5);
+        assert_eq!(flat.factor("trace"), 0.5);
+    }
+
+    #[test]
+    fn estimated_guesses_grow_with_the_candidates() {
+        assert_eq!(estimated_guesses(0), 0.0);
+        assert_eq!(estimated_guesses(1), 1.0);
+        for n in 1..3000 {
+            assert!(estimated_guesses(n + 1) > estimated_guesses(n), "{}", n);
+        }
+    }
+
+    #[test]
+    fn word_list_keeps_five_lowercase_letters() {
+        let (list, rejected) = parse_word_list("crane\n  slate \n\nCRANE\ncafés\nfights\nab\n\n");
+        assert_eq!(list, words(&["crane", "slate"]).into_iter().collect());
+        // Blank lines aren't counted as rejected
+        assert_eq!(rejected, 4);
+    }
+
+    #[test]
+    fn frequency_tokens_are_normalized() {
+        assert_eq!(
+            normalize_frequency_token("Crane,"),
+            Some("crane".to_string())
+        );
+        assert_eq!(
+            normalize_frequency_token("CRANE"),
+            Some("crane".to_string())
+        );
+        assert_eq!(
+            normalize_frequency_token("crane'"),
+            Some("crane".to_string())
+        );
+        assert_eq!(normalize_frequency_token("don't"), None);
+        assert_eq!(normalize_frequency_token("..."), None);
+    }
+
+    #[test]
+    fn repeated_yellows_need_as_many_copies() {
+        let feedback = feedback_for("speed", "erase");
+        assert_eq!(feedback, "yxyyx");
+        let mut state = GameState::new();
+        state.update("speed", &feedback);
+        assert_eq!(state.min_counts.get(&'e'), Some(&2));
+        assert!(state.matches("erase"));
+        assert!(state.matches("verse"));
+        // Fits every other constraint but has only one E
+        assert!(!state.matches("raise"));
+    }
+
+    #[test]
+    fn unknown_tiles_add_no_constraint() {
+        let mut state = GameState::new();
+        state.update("crane", "?????");
+        assert!(!state.has_constraints());
+        assert!(state.min_counts.is_empty() && state.max_counts.is_empty());
+
+        // The unknown E might be the answer's second E, so the grey one caps E at two
+        let mut state = GameState::new();
+        state.update("geese", "x?gxx");
+        assert!(state.yellows_not_at[1].is_empty());
+        assert_eq!(state.min_counts.get(&'e'), Some(&1));
+        assert_eq!(state.max_counts.get(&'e'), Some(&2));
+        assert!(!state.excluded_letters.contains(&'e'));
+        assert!(state.matches("fleet"));
+        assert!(state.matches("cheap"));
+    }
+
+    #[test]
+    fn word_source_reads_json_and_lines() {
+        let (list, rejected) = parse_word_source(r#"[" Crane", "slate", "ab", "café"]"#);
+        assert_eq!(list, words(&["crane", "slate"]).into_iter().collect());
+        assert_eq!(rejected, 2);
+
+        let (list, rejected) = parse_word_source("crane\nslate\nab\n");
+        assert_eq!(list, words(&["crane", "slate"]).into_iter().collect());
+        assert_eq!(rejected, 1);
+
+        // Not valid JSON, so read as lines, none of which is a word
+        let (list, rejected) = parse_word_source("[\"crane\",\n\"slate\"");
+        assert!(list.is_empty());
+        assert_eq!(rejected, 2);
+    }
+
+    #[test]
+    fn frequencies_without_a_scale_give_no_commonality() {
+        let list = words(&["crane", "slate"]);
+        let wanted: HashSet<&String> = list.iter().collect();
+
+        let data = parse_frequency_data("crane 1\nslate 1\nthe 1\n", &wanted);
+        assert!(data.commonality.is_empty());
+        assert!(data.dictionary.contains("the"));
+
+        let data = parse_frequency_data("crane 100\nslate 10\n", &wanted);
+        assert_eq!(data.commonality["crane"], 1.0);
+        assert_eq!(data.commonality["slate"], 0.5);
+    }
+
+    #[test]
+    fn guessed_words_are_not_suggested() {
+        let list = words(&["crane", "slate", "trace"]);
+        let history = vec![("crane".to_string(), "?????".to_string())];
+        let result =
+            suggest_with(&history, &SuggestOptions::default(), &list, &HashMap::new()).unwrap();
+        assert_eq!(result.candidate_count, 2);
+        assert!(result.suggestions.iter().all(|(w, _)| w != "crane"));
+
+        // Only a miss counts as guessed
+        let mut state = GameState::new();
+        state.update("crane", "ggggg");
+        assert!(state.guessed.is_empty());
+    }
+
+    #[test]
+    fn verify_path_checks_every_step() {
+        let step = |guess: &str, answer: &str| (guess.to_string(), feedback_for(guess, answer));
+        let game = vec![
+            step("slate", "crate"),
+            step("trace", "crate"),
+            step("crate", "crate"),
+        ];
+        assert_eq!(verify_path(&game, "crate"), Ok(()));
+
+        let mut wrong = game.clone();
+        wrong[1].1 = "ggggg".to_string();
+        let error = verify_path(&wrong, "crate").unwrap_err();
+        assert!(error.starts_with("Guess 2 (trace)"), "{}", error);
+
+        assert!(verify_path(&game[..2], "crate").is_err());
+        assert!(verify_path(&[], "crate").is_err());
+    }
+
+    #[cfg(feature = "fetch")]
+    #[test]
+    fn error_responses_keep_the_cache() {
+        let mut server = mockito::Server::new();
+        let missing = server.mock("GET", "/missing").with_status(404).create();
+        let failing = server
+            .mock("GET", "/failing")
+            .with_status(500)
+            .expect(1 + FETCH_RETRIES as usize)
+            .create();
+
+        for path in ["/missing", "/failing"] {
+            let url = format!("{}{}", server.url(), path);
+            let (body_path, meta_path) = cache_paths(&url);
+            std::fs::create_dir_all(cache_dir()).unwrap();
+            std::fs::write(&body_path, "crane\n").unwrap();
+
+            assert!(fetch_cached(&url).unwrap_err().starts_with("HTTP "));
+            assert_eq!(std::fs::read_to_string(&body_path).unwrap(), "crane\n");
+            let _ = std::fs::remove_file(body_path);
+            let _ = std::fs::remove_file(meta_path);
+        }
+        missing.assert();
+        failing.assert();
+    }
+
+    #[test]
+    fn guess_input_errors_name_the_problem() {
+        assert_eq!(check_guess_input("crane"), Ok(()));
+        assert_eq!(
+            check_guess_input("café"),
+            Err(
+                "'é' isn't a plain letter: use a-z only (no accented or full-width characters)."
+                    .to_string()
+            )
+        );
+        // Five characters but not five ASCII letters
+        assert_eq!(
+            check_guess_input("ｃｒａｎｅ"),
+            Err(
+                "'ｃ' isn't a plain letter: use a-z only (no accented or full-width characters)."
+                    .to_string()
+            )
+        );
+        assert_eq!(
+            check_guess_input("cran"),
+            Err("Guess must be exactly 5 letters (got 4).".to_string())
+        );
+        assert_eq!(
+            check_guess_input("cr4ne"),
+            Err("Guess must be exactly 5 lowercase letters.".to_string())
+        );
+        assert!(check_feedback_input("ｇｇｇｇｇ")
+            .unwrap_err()
+            .starts_with("'ｇ' isn't a feedback tile"));
+    }
+
+    #[test]
+    fn best_opener_matches_the_full_search_on_a_small_pool() {
+        let list = words(&[
+            "crane", "crate", "trace", "slate", "stale", "fight", "light", "might", "night",
+        ]);
+        let refs: Vec<&String> = list.iter().collect();
+        let commonality = HashMap::new();
+        assert!(refs.len() <= OPENER_SHORTLIST);
+        assert_eq!(
+            best_opener(&refs, &commonality),
+            best_candidate_guess(&refs, &commonality)
+        );
+        assert_eq!(best_opener(&[], &commonality), None);
+    }
+
+    proptest! {
+        #[test]
+        fn true_feedback_keeps_the_answer(guess in word(), answer in word(), others in proptest::collection::vec(word(), 20)) {
+            let feedback = feedback_for(&guess, &answer);
+            let mut state = GameState::new();
+            state.update(&guess, &feedback);
+            prop_assert!(state.matches(&answer));
+            // Any other word stays exactly when it would have given the same feedback
+            for other in &others {
+                prop_assert_eq!(state.matches(other), feedback_for(&guess, other) == feedback, "{} vs {}", guess, other);
+            }
+        }
+    }
 }
@@ -1,6 +1,10 @@
+use itertools::Itertools;
+use serde::Deserialize;
 use std::{
-    collections::HashSet,
+    collections::{HashMap, HashSet},
+    fs::File,
     io::{self, Write},
+    path::{Path, PathBuf},
 };
 use wordle_word::*;
 
@@ -13,6 +17,19 @@ fn read_line() -> String {
     input.trim().to_string()
 }
 
+/// Ask for the secret answer in `--vs` mode without echoing it, so the player typing
+/// guesses can't see it.
+fn read_secret(all: &HashSet<String>) -> Result<String, String> {
+    let secret = rpassword::prompt_password("Secret word (hidden): ")
+        .map_err(|e| format!("Couldn't read the secret word: {}", e))?
+        .trim()
+        .to_ascii_lowercase();
+    if !all.contains(&secret) {
+        return Err("The secret word is not in the word list.".to_string());
+    }
+    Ok(secret)
+}
+
 fn print_help() {
     println!();
     println!("Usage:");
@@ -20,27 +37,1033 @@ fn print_help() {
     println!("    g = green  (correct letter, correct position)");
     println!("    y = yellow (correct letter, wrong position)");
     println!("    x = grey   (letter not in the word)");
+    println!("    ? = unknown (tile color not known; adds no constraint)");
     println!();
     println!("  Example: if you guessed 'crane' and got green-yellow-grey-grey-green,");
     println!("           enter feedback: gyxxg");
+    println!("  Or enter both on one line: crane gyxxg");
     println!();
     println!("  Commands:");
     println!("    q = quit");
     println!("    ? = show this help");
     println!("    s = show current constraints");
+    println!("    yellows = group the candidates by where they put each unplaced yellow");
+    println!("    mode <freq|entropy|minimax|challenge|common|split> = switch ranking strategy");
+    println!("    analyze [word] = every feedback the top pick (or word) could get, with odds");
+    println!("    compare <word1> <word2> = compare two guesses side by side");
+    println!("    fork <strategy> <strategy> = show two strategies' suggestions side by side");
+    println!("    back <n> = roll the game back to just after guess n (0 starts over)");
+    println!("    set <n>=<letter> = mark a letter known at position n, e.g. set 1=c");
+}
+
+/// The common strategy needs commonality data; without it, say it falls back.
+fn warn_if_no_commonality(strategy: Strategy, commonality: &HashMap<String, f64>) {
+    if strategy == Strategy::Common && commonality.is_empty() {
+        eprintln!("Warning: no word frequency data loaded, so common mode ranks by letter score.");
+    }
+}
+
+/// Parse a `set` spec like `1=c` into a 0-based position and letter.
+fn parse_known_letter(spec: &str) -> Option<(usize, char)> {
+    let (pos, letter) = spec.trim().split_once('=')?;
+    let pos = pos.trim().parse::<usize>().ok()?.checked_sub(1)?;
+    let mut letters = letter.trim().chars();
+    match (letters.next(), letters.next()) {
+        (Some(c), None) => Some((pos, c.to_ascii_lowercase())),
+        _ => None,
+    }
+}
+
+/// The alphabet with each letter's status, like Wordle's keyboard: `[C]` green, `(E)`
+/// yellow, `-` grey and plain lowercase for untested letters.
+fn alphabet_line(state: &GameState) -> String {
+    let statuses = state.letter_statuses();
+    ('a'..='z')
+        .map(|letter| match statuses[&letter] {
+            LetterStatus::Green => format!("[{}]", letter.to_ascii_uppercase()),
+            LetterStatus::Yellow => format!("({})", letter.to_ascii_uppercase()),
+            LetterStatus::Grey => "-".to_string(),
+            LetterStatus::Untested => letter.to_string(),
+        })
+        .join(" ")
+}
+
+fn display_suggestions(
+    ranked: &[(&String, f64, usize)],
+    candidates: &[&String],
+    options: &CliOptions,
+    state: &GameState,
+) {
+    let top = ranked.iter().map(|r| r.1).fold(0.0, f64::max);
+    let window = &ranked[..ranked
+        .len()
+        .min(options.hints.suggestion_limit(options.limit))];
+    let floor = options.min_score.unwrap_or(0.0);
+    let shown: Vec<&(&String, f64, usize)> = window
+        .iter()
+        .filter(|(_, score, _)| meets_score_floor(*score, top, floor))
+        .collect();
+    let hidden = window.len() - shown.len();
+    // Only worth marking the possible answers when pure probes are mixed in
+    let could_win = |word: &String| candidates.contains(&word);
+    let mark_winners = shown.iter().any(|(w, _, _)| !could_win(w));
+    for (i, (word, score, anagrams)) in shown.iter().enumerate() {
+        let winner = if mark_winners && could_win(word) {
+            " \u{2605}"
+        } else {
+            ""
+        };
+        let marker = if options.favorites.contains(*word) {
+            "  [favorite]"
+        } else {
+            ""
+        };
+        let grouped = match anagrams {
+            0 => String::new(),
+            1 => "  +1 anagram".to_string(),
+            n => format!("  +{} anagrams", n),
+        };
+        let score = if options.normalized_scores {
+            format!("{:.0} | {:.2}", relative_score(*score, top), score)
+        } else {
+            format!("{:.2}", score)
+        };
+        let (new_letters, _) = state.letter_coverage(word);
+        let remaining = if options.hints == HintLevel::Full {
+            format!("  leaves ~{:.1}", expected_remaining(word, candidates))
+        } else {
+            String::new()
+        };
+        println!(
+            "  {:>2}. {}{}  ({})  tests {} new letter{}{}{}{}",
+            i + 1,
+            word,
+            winner,
+            score,
+            new_letters,
+            if new_letters == 1 { "" } else { "s" },
+            remaining,
+            grouped,
+            marker
+        );
+    }
+    if hidden > 0 {
+        println!(
+            "  ... and {} more low-value option{}",
+            hidden,
+            if hidden == 1 { "" } else { "s" }
+        );
+    }
+}
+
+/// Rank the candidates, pin favorites to the top and print the suggestion list. With
+/// `--fresh`, words from `guessable` made only of untested letters are listed instead; with
+/// `--focus-position`, words that best pin down the most uncertain open position.
+fn show_suggestions(
+    candidates: &[&String],
+    guessable: &HashSet<String>,
+    commonality: &HashMap<String, f64>,
+    state: &GameState,
+    options: &CliOptions,
+) {
+    if options.hints == HintLevel::CountOnly {
+        println!("  (hidden at hint level 0)");
+        return;
+    }
+    // Before the first guess every letter is untested, so the normal ranking applies
+    if options.fresh && state.has_constraints() && candidates.len() > 2 {
+        let words: Vec<&String> = guessable.iter().sorted().collect();
+        let fresh = fresh_guesses(&words, candidates, state);
+        if fresh.len() >= FRESH_MIN_WORDS {
+            let ranked: Vec<(&String, f64, usize)> =
+                fresh.into_iter().map(|(w, s)| (w, s, 0)).collect();
+            display_suggestions(&ranked, candidates, options, state);
+            return;
+        }
+        println!("  (too few words use only untested letters; showing the usual suggestions)");
+    }
+    if options.focus_position && state.has_constraints() && candidates.len() > 2 {
+        if let Some((position, _)) = uncertain_position(candidates, state) {
+            let letters = candidates
+                .iter()
+                .map(|w| w.as_bytes()[position])
+                .unique()
+                .count();
+            println!(
+                "  (focusing on position {}: {} letters still possible)",
+                position + 1,
+                letters
+            );
+            let words: Vec<&String> = guessable.iter().sorted().collect();
+            let ranked: Vec<(&String, f64, usize)> =
+                focus_position_guesses(&words, candidates, position)
+                    .into_iter()
+                    .map(|(w, s)| (w, s, 0))
+                    .collect();
+            display_suggestions(&ranked, candidates, options, state);
+            return;
+        }
+    }
+    let ranked = rank_words_with(candidates, commonality, state, &options.rank);
+    let ranked = finish_ranking(ranked, candidates.len(), state, options);
+    display_suggestions(&ranked, candidates, options, state);
+}
+
+/// Apply the display options to a ranking: drop guessed words (and non-human openers
+/// with `--human-openers`), shuffle ties with `--seed`, demote and pin favorites, then
+/// `--diverse` and `--group-anagrams`. Each entry carries its grouped anagram count.
+fn finish_ranking<'a>(
+    mut ranked: Vec<(&'a String, f64)>,
+    candidate_count: usize,
+    state: &GameState,
+    options: &CliOptions,
+) -> Vec<(&'a String, f64, usize)> {
+    ranked.retain(|(w, _)| !state.guessed.contains(*w));
+    if options.human_openers && !state.has_constraints() {
+        ranked.retain(|(w, _)| is_human_opener(w, options.max_consonant_run));
+    }
+    if let Some(seed) = options.seed {
+        // Mix in the candidate count so each step of a game draws a different shuffle
+        shuffle_ties(&mut ranked, seed ^ candidate_count as u64);
+    }
+    demote_words(&mut ranked, &options.demoted);
+    pin_favorites(&mut ranked, &options.favorites);
+    if let Some(min_difference) = options.diverse {
+        ranked = diverse_selection(ranked, options.limit, min_difference);
+    }
+    if options.group_anagrams {
+        group_anagrams(ranked)
+    } else {
+        ranked.into_iter().map(|(w, s)| (w, s, 0)).collect()
+    }
+}
+
+/// The word `show_suggestions` would list first (ignoring `--fresh` and friends).
+fn top_pick(
+    candidates: &[&String],
+    commonality: &HashMap<String, f64>,
+    state: &GameState,
+    options: &CliOptions,
+) -> Option<String> {
+    let mut ranked = rank_words_with(candidates, commonality, state, &options.rank);
+    ranked.retain(|(w, _)| !state.guessed.contains(*w));
+    demote_words(&mut ranked, &options.demoted);
+    pin_favorites(&mut ranked, &options.favorites);
+    ranked.first().map(|(w, _)| w.to_string())
+}
+
+/// Print every feedback `word` could get against the candidates, likeliest first.
+fn show_outcomes(word: &str, candidates: &[&String]) {
+    let outcomes = outcome_distribution(word, candidates);
+    println!(
+        "\n{}: {} possible outcome{} over {} candidates, ~{:.1} left on average",
+        word,
+        outcomes.len(),
+        if outcomes.len() == 1 { "" } else { "s" },
+        candidates.len(),
+        expected_remaining(word, candidates)
+    );
+    for outcome in outcomes.iter().take(OUTCOME_LIMIT) {
+        let result = if outcome.feedback == "ggggg" {
+            "solved".to_string()
+        } else {
+            format!("leaves {}", outcome.remaining)
+        };
+        println!(
+            "  {}  {:>5.1}%  {}",
+            outcome.feedback,
+            100.0 * outcome.probability,
+            result
+        );
+    }
+    if outcomes.len() > OUTCOME_LIMIT {
+        println!(
+            "  ... and {} rarer outcomes",
+            outcomes.len() - OUTCOME_LIMIT
+        );
+    }
+}
+
+/// Most outcomes `analyze` lists; the rest are counted.
+const OUTCOME_LIMIT: usize = 20;
+
+/// Print a two-column comparison of two guesses against the current candidates.
+fn show_comparison(a: &GuessComparison, b: &GuessComparison) {
+    let yes_no = |v: bool| if v { "yes" } else { "no" };
+    println!();
+    println!("  {:<20} {:>10} {:>10}", "", a.word, b.word);
+    println!(
+        "  {:<20} {:>10.1} {:>10.1}",
+        "Expected remaining", a.expected_remaining, b.expected_remaining
+    );
+    println!(
+        "  {:<20} {:>10} {:>10}",
+        "Worst case", a.worst_case, b.worst_case
+    );
+    println!(
+        "  {:<20} {:>10} {:>10}",
+        "Could be the answer",
+        yes_no(a.is_candidate),
+        yes_no(b.is_candidate)
+    );
+    println!(
+        "  {:<20} {:>10.2} {:>10.2}",
+        "Commonality", a.commonality, b.commonality
+    );
+}
+
+/// Rank the same constraints under two strategies and print their suggestions side by
+/// side, each finished like `show_suggestions`' list. Each side ranks its own copy of the
+/// game state.
+fn show_fork(
+    candidates: &[&String],
+    commonality: &HashMap<String, f64>,
+    state: &GameState,
+    options: &CliOptions,
+    strategies: [Strategy; 2],
+) {
+    let [left, right] = strategies.map(|strategy| {
+        let forked = state.clone();
+        let rank = RankOptions {
+            strategy,
+            ..options.rank.clone()
+        };
+        let ranked = rank_words_with(candidates, commonality, &forked, &rank);
+        let mut ranked = finish_ranking(ranked, candidates.len(), &forked, options);
+        ranked.truncate(options.limit);
+        ranked
+    });
+
+    println!();
+    println!(
+        "      {:<18} {:<18}",
+        strategies[0].name(),
+        strategies[1].name()
+    );
+    let cell = |entry: Option<&(&String, f64, usize)>| {
+        entry.map_or(String::new(), |(word, score, anagrams)| match anagrams {
+            0 => format!("{}  ({:.2})", word, score),
+            n => format!("{} +{}  ({:.2})", word, n, score),
+        })
+    };
+    for i in 0..left.len().max(right.len()) {
+        println!(
+            "  {:>2}. {:<18} {:<18}",
+            i + 1,
+            cell(left.get(i)),
+            cell(right.get(i))
+        );
+    }
+}
+
+// ---------- Options ----------
+
+enum Command {
+    Interactive,
+    Solve(Vec<String>),
+    DumpMatrix(PathBuf),
+    Audit,
+    Tree(String),
+    Version,
+    Sources,
+}
+
+struct CliOptions {
+    command: Command,
+    rank: RankOptions,
+    trace: bool,
+    /// Stream one JSON object per answer from `solve` (`--jsonl`).
+    jsonl: bool,
+    matrix_all_guesses: bool,
+    fixed_opener: Option<String>,
+    favorites: HashSet<String>,
+    /// Words never to treat as answers (`--never-answers FILE`).
+    never_answers: HashSet<String>,
+    /// After a win, offer another game with the same data (`--continuous`).
+    continuous: bool,
+    /// Suggest guesses made only of untested letters (`--fresh`).
+    fresh: bool,
+    /// Suggest guesses that pin down the most uncertain open position (`--focus-position`).
+    focus_position: bool,
+    /// Play a multi-board game (Dordle, Quordle, ...) with this many boards (`--boards`).
+    boards: Option<usize>,
+    /// How much help to show (`--hints 0..3`).
+    hints: HintLevel,
+    /// Only exclude past answers from the last N days (`--recent-days N`).
+    recent_days: Option<u64>,
+    /// Restrict candidates to these words (`--known-words FILE`).
+    known_words: Option<HashSet<String>>,
+    save_path: Option<PathBuf>,
+    load_path: Option<PathBuf>,
+    past_pattern_weight: Option<f64>,
+    /// Rank past answers last instead of removing them (`--soft-exclude`).
+    soft_exclude: bool,
+    /// Words moved to the end of the suggestions: the past answers under `--soft-exclude`.
+    demoted: HashSet<String>,
+    /// Weight of the answer positional-frequency bonus (`--answer-positions W`).
+    answer_position_weight: Option<f64>,
+    guesses: Vec<(String, String)>,
+    status: bool,
+    /// Print the constraints as a grep-able regex and exit (`--regex`).
+    regex: bool,
+    /// Guesses allowed before warning that the game is lost (`--max-guesses N`).
+    max_guesses: usize,
+    /// Two-player mode: read a secret answer, then compute each guess's feedback (`--vs`).
+    vs: bool,
+    /// Suggestions printed per step (`--top N`, or every candidate with `--all`).
+    limit: usize,
+    /// Shuffle tied suggestions with this seed (`--seed`); off by default.
+    seed: Option<u64>,
+    group_anagrams: bool,
+    /// Minimum letter difference between shown suggestions (`--diverse [N]`).
+    diverse: Option<usize>,
+    /// Only suggest starters with a vowel (`--human-openers`), optionally capping
+    /// consonant runs (`--max-consonant-run N`).
+    human_openers: bool,
+    max_consonant_run: Option<usize>,
+    /// Show scores as 0-100 relative to the turn's best, next to the raw score.
+    normalized_scores: bool,
+    /// Hide suggestions scoring under this percentage of the turn's best (`--min-score`).
+    min_score: Option<f64>,
+    /// Name the trickiest possible answer after each guess (`--trickiest`).
+    trickiest: bool,
+}
+
+/// Parse a flag's non-negative numeric value.
+fn parse_weight(flag: &str, value: Option<String>) -> Result<f64, String> {
+    let value = value.ok_or(format!("{} requires a value", flag))?;
+    value
+        .parse::<f64>()
+        .ok()
+        .filter(|w| *w >= 0.0)
+        .ok_or(format!("Invalid {} value: {}", flag, value))
+}
+
+fn parse_args() -> Result<CliOptions, String> {
+    let mut options = CliOptions {
+        command: Command::Interactive,
+        rank: RankOptions::default(),
+        trace: false,
+        jsonl: false,
+        matrix_all_guesses: false,
+        fixed_opener: None,
+        favorites: HashSet::new(),
+        never_answers: HashSet::new(),
+        known_words: None,
+        recent_days: None,
+        hints: HintLevel::default(),
+        fresh: false,
+        continuous: false,
+        focus_position: false,
+        boards: None,
+        save_path: None,
+        load_path: None,
+        past_pattern_weight: None,
+        answer_position_weight: None,
+        soft_exclude: false,
+        demoted: HashSet::new(),
+        guesses: Vec::new(),
+        status: false,
+        regex: false,
+        vs: false,
+        max_guesses: MAX_GUESSES,
+        limit: 15,
+        seed: None,
+        group_anagrams: false,
+        diverse: None,
+        human_openers: false,
+        max_consonant_run: None,
+        normalized_scores: false,
+        min_score: None,
+        trickiest: false,
+    };
+
+    let mut args = std::env::args().skip(1).peekable();
+    match args.peek().map(String::as_str) {
+        Some("solve") => {
+            args.next();
+            options.command = Command::Solve(Vec::new());
+        }
+        Some("audit") => {
+            args.next();
+            options.command = Command::Audit;
+        }
+        Some("tree") => {
+            args.next();
+            let opener = args.next().ok_or("Usage: tree <opener>")?;
+            options.command = Command::Tree(opener.to_ascii_lowercase());
+        }
+        _ => {}
+    }
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--version" => options.command = Command::Version,
+            "--sources" => options.command = Command::Sources,
+            "--trace" => options.trace = true,
+            "--jsonl" => options.jsonl = true,
+            "--status" => options.status = true,
+            "--regex" => options.regex = true,
+            "--vs" => options.vs = true,
+            "--max-guesses" => {
+                let value = args.next().ok_or("--max-guesses requires a number")?;
+                options.max_guesses =
+                    value
+                        .parse::<usize>()
+                        .ok()
+                        .filter(|n| *n >= 1)
+                        .ok_or(format!(
+                            "Invalid --max-guesses value: {} (must be at least 1)",
+                            value
+                        ))?;
+            }
+            "--guess" => {
+                let value = args.next().ok_or("--guess requires guess:feedback")?;
+                let (guess, feedback) = value
+                    .to_ascii_lowercase()
+                    .split_once(':')
+                    .map(|(g, f)| (g.to_string(), f.to_string()))
+                    .ok_or(format!("--guess expects guess:feedback, got {}", value))?;
+                if !is_valid_word(&guess) {
+                    return Err(format!("Invalid --guess word: {}", guess));
+                }
+                if !is_valid_feedback(&feedback) {
+                    return Err(format!("Invalid --guess feedback: {}", feedback));
+                }
+                options.guesses.push((guess, feedback));
+            }
+            "--dump-matrix" => {
+                let path = args.next().ok_or("--dump-matrix requires a path")?;
+                options.command = Command::DumpMatrix(PathBuf::from(path));
+            }
+            "--matrix-all-guesses" => options.matrix_all_guesses = true,
+            "--favorites" => {
+                let path = args.next().ok_or("--favorites requires a path")?;
+                let content = std::fs::read_to_string(&path)
+                    .map_err(|e| format!("Couldn't read favorites file {}: {}", path, e))?;
+                options.favorites = parse_word_list(&content).0;
+            }
+            "--never-answers" => {
+                let path = args.next().ok_or("--never-answers requires a path")?;
+                let content = std::fs::read_to_string(&path)
+                    .map_err(|e| format!("Couldn't read never-answers file {}: {}", path, e))?;
+                options.never_answers = parse_word_list(&content).0;
+            }
+            "--fresh" => options.fresh = true,
+            "--continuous" => options.continuous = true,
+            "--focus-position" => options.focus_position = true,
+            "--hints" => {
+                let value = args.next().ok_or("--hints requires a level from 0 to 3")?;
+                options.hints = value.parse()?;
+            }
+            "--boards" => {
+                let value = args.next().ok_or("--boards requires a number")?;
+                let count: usize = value
+                    .parse()
+                    .map_err(|_| format!("--boards expects a number, got {}", value))?;
+                if !(2..=MAX_BOARDS).contains(&count) {
+                    return Err(format!(
+                        "--boards expects 2 to {}, got {}",
+                        MAX_BOARDS, count
+                    ));
+                }
+                options.boards = Some(count);
+            }
+            "--recent-days" => {
+                let value = args.next().ok_or("--recent-days requires a number")?;
+                options.recent_days = Some(
+                    value
+                        .parse()
+                        .map_err(|_| format!("--recent-days expects a number, got {}", value))?,
+                );
+            }
+            "--known-words" => {
+                let path = args.next().ok_or("--known-words requires a path")?;
+                let content = std::fs::read_to_string(&path)
+                    .map_err(|e| format!("Couldn't read known-words file {}: {}", path, e))?;
+                options.known_words = Some(parse_word_list(&content).0);
+            }
+            "--save" => {
+                let path = args.next().ok_or("--save requires a path")?;
+                options.save_path = Some(PathBuf::from(path));
+            }
+            "--load" => {
+                let path = args.next().ok_or("--load requires a path")?;
+                options.load_path = Some(PathBuf::from(path));
+            }
+            "--fixed-opener" => {
+                let word = args
+                    .next()
+                    .ok_or("--fixed-opener requires a word")?
+                    .to_ascii_lowercase();
+                if !is_valid_word(&word) {
+                    return Err(format!("Invalid --fixed-opener word: {}", word));
+                }
+                options.fixed_opener = Some(word);
+            }
+            "--top" => {
+                let value = args.next().ok_or("--top requires a number")?;
+                options.limit = value
+                    .parse::<usize>()
+                    .ok()
+                    .filter(|n| *n >= 1)
+                    .ok_or(format!(
+                        "Invalid --top value: {} (must be at least 1)",
+                        value
+                    ))?;
+            }
+            "--all" => options.limit = usize::MAX,
+            "--group-anagrams" => options.group_anagrams = true,
+            "--normalized-scores" => options.normalized_scores = true,
+            "--min-score" => {
+                let value = args.next().ok_or("--min-score requires a percentage")?;
+                let floor: f64 = value
+                    .parse()
+                    .map_err(|_| format!("--min-score expects a percentage, got {}", value))?;
+                if !(0.0..=100.0).contains(&floor) {
+                    return Err(format!("--min-score expects 0 to 100, got {}", value));
+                }
+                options.min_score = Some(floor);
+            }
+            "--trickiest" => options.trickiest = true,
+            "--diverse" => {
+                let min_difference = args.peek().and_then(|v| v.parse::<usize>().ok());
+                if min_difference.is_some() {
+                    args.next();
+                }
+                options.diverse = Some(min_difference.unwrap_or(2));
+            }
+            "--seed" => {
+                let value = args.next().ok_or("--seed requires a number")?;
+                options.seed = Some(
+                    value
+                        .parse()
+                        .map_err(|_| format!("Invalid --seed value: {}", value))?,
+                );
+            }
+            "--human-openers" => options.human_openers = true,
+            "--max-consonant-run" => {
+                let value = args.next().ok_or("--max-consonant-run requires a number")?;
+                options.max_consonant_run = Some(
+                    value
+                        .parse()
+                        .map_err(|_| format!("Invalid --max-consonant-run value: {}", value))?,
+                );
+                options.human_openers = true;
+            }
+            "--yellow-bonus" => {
+                options.rank.yellow_bonus_weight = parse_weight(&arg, args.next())?;
+            }
+            "--past-pattern-bias" => {
+                options.past_pattern_weight = Some(parse_weight(&arg, args.next())?);
+            }
+            "--soft-exclude" => options.soft_exclude = true,
+            "--answer-positions" => {
+                options.answer_position_weight = Some(parse_weight(&arg, args.next())?);
+            }
+            "--common-threshold" => {
+                options.rank.common_threshold = parse_weight(&arg, args.next())?;
+            }
+            "--challenge" => options.rank.strategy = Strategy::Challenge,
+            "--mode" => {
+                let name = args.next().ok_or("--mode requires a strategy name")?;
+                options.rank.strategy = name.parse()?;
+            }
+            "--rare-bigrams" => {
+                options.rank.rare_bigram_threshold = Some(parse_weight(&arg, args.next())?);
+            }
+            _ => match &mut options.command {
+                Command::Solve(answers) if !arg.starts_with('-') => {
+                    answers.push(arg.to_ascii_lowercase())
+                }
+                _ => return Err(format!("Unknown argument: {}", arg)),
+            },
+        }
+    }
+
+    if matches!(&options.command, Command::Solve(answers) if answers.is_empty()) {
+        return Err("Usage: solve <answer>... [--trace] [--jsonl]".to_string());
+    }
+
+    Ok(options)
+}
+
+// ---------- Self-play ----------
+
+fn run_solve(
+    answers: &[String],
+    candidates: &[&String],
+    commonality: &HashMap<String, f64>,
+    options: &CliOptions,
+) {
+    let mut solved = 0;
+    let mut total_guesses = 0;
+
+    for answer in answers {
+        if !candidates.contains(&answer) {
+            if options.jsonl {
+                eprintln!("{}: not in the candidate list, skipped", answer);
+            } else {
+                println!("{}: not in the candidate list, skipped", answer);
+            }
+            continue;
+        }
+
+        let steps = auto_solve(
+            answer,
+            candidates,
+            commonality,
+            &options.rank,
+            options.fixed_opener.as_deref(),
+            20,
+        );
+        let path: Vec<&str> = steps.iter().map(|s| s.guess.as_str()).collect();
+        let is_solved = steps.last().is_some_and(|s| s.feedback == "ggggg");
+
+        if options.jsonl {
+            // Flush each line so consumers can process results as they arrive
+            let line = serde_json::json!({
+                "answer": answer,
+                // Candidates left before each guess, for fitting `estimated_guesses`
+                "candidates": steps.iter().map(|s| s.candidates_before).collect::<Vec<_>>(),
+                "guess_count": steps.len(),
+                "guesses": path,
+                "solved": is_solved,
+                "strategy": options.rank.strategy.name(),
+            });
+            let mut stdout = io::stdout().lock();
+            if writeln!(stdout, "{}", line)
+                .and_then(|_| stdout.flush())
+                .is_err()
+            {
+                // The reader went away (e.g. `| head`)
+                return;
+            }
+            continue;
+        }
+
+        if options.trace {
+            println!("{}:", answer);
+            for (i, step) in steps.iter().enumerate() {
+                println!(
+                    "  {}. {}  score {:.2}  feedback {}  candidates {} -> {}",
+                    i + 1,
+                    step.guess,
+                    step.score,
+                    step.feedback,
+                    step.candidates_before,
+                    step.candidates_after
+                );
+            }
+        }
+
+        if is_solved {
+            solved += 1;
+            total_guesses += steps.len();
+            println!("{}: {} guesses ({})", answer, steps.len(), path.join(" "));
+        } else {
+            println!("{}: not solved ({})", answer, path.join(" "));
+        }
+    }
+
+    if solved > 0 && !options.jsonl {
+        println!(
+            "\nSolved {}/{}, average {:.2} guesses.",
+            solved,
+            answers.len(),
+            total_guesses as f64 / solved as f64
+        );
+    }
+}
+
+// ---------- Multi-board ----------
+
+/// Most boards `--boards` accepts, as in Octordle.
+const MAX_BOARDS: usize = 8;
+
+/// Play a multi-board game: one guess per turn, with feedback entered for each unsolved
+/// board, and suggestions ranked across all of them.
+fn run_boards(
+    count: usize,
+    candidates: &[&String],
+    guessable: &HashSet<String>,
+    options: &CliOptions,
+) {
+    let mut game = MultiBoard::new(count);
+    println!(
+        "Playing {} boards. Enter one guess, then each board's feedback.",
+        count
+    );
+
+    while !game.is_solved() {
+        let unsolved = game.unsolved();
+        let board_candidates: Vec<Vec<&String>> = unsolved
+            .iter()
+            .map(|&i| {
+                candidates
+                    .iter()
+                    .copied()
+                    .filter(|w| game.boards[i].matches(w))
+                    .collect()
+            })
+            .collect();
+
+        println!();
+        for (&i, remaining) in unsolved.iter().zip(&board_candidates) {
+            if remaining.len() <= 5 {
+                println!(
+                    "Board {}: {} candidate{} ({})",
+                    i + 1,
+                    remaining.len(),
+                    if remaining.len() == 1 { "" } else { "s" },
+                    remaining.iter().join(", ")
+                );
+            } else {
+                println!("Board {}: {} candidates", i + 1, remaining.len());
+            }
+        }
+        if let Some((&i, _)) = unsolved
+            .iter()
+            .zip(&board_candidates)
+            .find(|(_, b)| b.is_empty())
+        {
+            println!(
+                "\nBoard {} has no candidates left -- double-check its feedback.",
+                i + 1
+            );
+            return;
+        }
+
+        if options.hints.shows_analysis() {
+            println!("\nTop suggestions:");
+            let limit = options.hints.suggestion_limit(options.limit);
+            for (n, (word, score)) in multi_board_guesses(&board_candidates)
+                .into_iter()
+                .take(limit)
+                .enumerate()
+            {
+                let boards = unsolved
+                    .iter()
+                    .zip(&board_candidates)
+                    .filter(|(_, b)| b.contains(&word))
+                    .map(|(&i, _)| (i + 1).to_string())
+                    .join(", ");
+                let answer_for = if boards.is_empty() {
+                    String::new()
+                } else {
+                    format!("  could be board {}", boards)
+                };
+                println!("  {:>2}. {}  ({:.2}){}", n + 1, word, score, answer_for);
+            }
+        }
+
+        print!("\nEnter guess (or 'q' to quit): ");
+        let guess = read_line().to_ascii_lowercase();
+        if guess == "q" || guess == "quit" {
+            return;
+        }
+        if let Err(e) = check_guess_input(&guess) {
+            println!("{}", e);
+            continue;
+        }
+        if !guessable.contains(&guess) {
+            println!("'{}' is not in the word list.", guess);
+            continue;
+        }
+
+        let mut feedbacks = Vec::new();
+        for &i in &unsolved {
+            let feedback = loop {
+                print!("Board {} feedback (g/y/x): ", i + 1);
+                let feedback = read_line().to_ascii_lowercase();
+                match check_feedback_input(&feedback) {
+                    Ok(()) => break feedback,
+                    Err(e) => println!("{}", e),
+                }
+            };
+            if feedback == "ggggg" {
+                println!("Board {} solved: {}", i + 1, guess);
+            }
+            feedbacks.push(feedback);
+        }
+        if let Err(e) = game.update(&guess, &feedbacks) {
+            println!("{}", e);
+        }
+    }
+
+    println!(
+        "\nAll {} boards solved in {} guesses!",
+        count,
+        game.guesses.len()
+    );
 }
 
-fn display_suggestions(ranked: &[(&String, f64)], limit: usize) {
-    for (i, (word, score)) in ranked.iter().take(limit).enumerate() {
-        println!("  {:>2}. {}  ({:.2})", i + 1, word, score);
+// ---------- Opener tree ----------
+
+/// Print the best second guess for each feedback `opener` can get, most likely first.
+fn run_tree(
+    opener: &str,
+    candidates: &[&String],
+    commonality: &HashMap<String, f64>,
+    options: &CliOptions,
+) {
+    let tree = opener_tree(opener, candidates, commonality, &options.rank);
+    let counts = candidates
+        .iter()
+        .counts_by(|answer| feedback_for(opener, answer));
+    let count = |feedback: &str| counts.get(feedback).copied().unwrap_or(0);
+
+    println!(
+        "Best second guess after {} for each of its {} possible feedbacks:",
+        opener,
+        tree.len()
+    );
+    for (feedback, guess) in tree
+        .iter()
+        .sorted_by(|a, b| count(b.0).cmp(&count(a.0)).then(a.0.cmp(b.0)))
+    {
+        println!(
+            "  {}  {}  ({} candidates)",
+            feedback,
+            guess,
+            count(feedback)
+        );
     }
 }
 
+// ---------- Data audit ----------
+
+/// Report past answers missing from the word list, and past answers the plural filter
+/// would have removed (which would mean the filter drops real answers).
+fn run_audit(all: &HashSet<String>, used: &HashSet<String>, dictionary: &HashSet<String>) {
+    let missing: Vec<&String> = used.iter().filter(|w| !all.contains(*w)).sorted().collect();
+    println!(
+        "{} past answer(s) missing from the word list: {}",
+        missing.len(),
+        missing.iter().join(" ")
+    );
+
+    let plurals: Vec<&String> = used
+        .iter()
+        .filter(|w| is_regular_plural(w, dictionary))
+        .sorted()
+        .collect();
+    println!(
+        "{} past answer(s) the plural filter would remove: {}",
+        plurals.len(),
+        plurals.iter().join(" ")
+    );
+}
+
+// ---------- Save files ----------
+
+/// An in-progress solve as written by `save_game` (`--save`) and resumed with `--load`.
+#[derive(Deserialize)]
+struct SaveFile {
+    state: GameState,
+    guesses: Vec<(String, String)>,
+}
+
+/// Count a solved game in `tally` (games, total guesses), print the running record and
+/// ask whether to play again.
+fn another_game(tally: &mut (usize, usize), guesses: usize) -> bool {
+    tally.0 += 1;
+    tally.1 += guesses;
+    println!(
+        "\nGames solved: {}, averaging {:.2} guesses.",
+        tally.0,
+        tally.1 as f64 / tally.0 as f64
+    );
+    print!("Solve another? (y/n): ");
+    matches!(read_line().to_ascii_lowercase().as_str(), "y" | "yes")
+}
+
+/// Print the version and configured data sources, with when each was last downloaded.
+fn print_sources() {
+    let sources = DataSources::configured();
+    let fetched = |location: &str| match cached_at(location) {
+        Some(t) => format!("fetched {}", format_timestamp(t)),
+        None if location.starts_with("http") => "not downloaded yet".to_string(),
+        None => "local file".to_string(),
+    };
+    println!("wordle_word {}", env!("CARGO_PKG_VERSION"));
+    println!(
+        "Word list:    {} ({})",
+        sources.word_list,
+        fetched(&sources.word_list)
+    );
+    println!(
+        "Past answers: {} ({})",
+        sources.past_answers,
+        fetched(&sources.past_answers)
+    );
+    for location in &sources.frequency {
+        println!("Frequency:    {} ({})", location, fetched(location));
+    }
+}
+
+fn save_game(path: &Path, state: &GameState, guesses: &[(String, String)]) {
+    let save = serde_json::json!({ "state": state, "guesses": guesses });
+    let result = serde_json::to_string_pretty(&save)
+        .map_err(io::Error::other)
+        .and_then(|json| std::fs::write(path, json));
+    if let Err(e) = result {
+        eprintln!("Warning: couldn't save game to {}: {}", path.display(), e);
+    }
+}
+
+fn load_game(path: &Path) -> Result<SaveFile, String> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| format!("Couldn't read {}: {}", path.display(), e))?;
+    serde_json::from_str(&content)
+        .map_err(|e| format!("Invalid save file {}: {}", path.display(), e))
+}
+
+// ---------- Feedback matrix export ----------
+
+/// Write the guess × answer feedback matrix. The file starts with a text header
+/// (`wordle-feedback-matrix 1`, then `guesses N` and `answers M` each followed by a line
+/// of space-separated words in matrix order), followed by N×M bytes in row-major order.
+fn write_matrix(path: &Path, guesses: &[&String], answers: &[&String]) -> io::Result<()> {
+    let mut out = io::BufWriter::new(File::create(path)?);
+    writeln!(out, "wordle-feedback-matrix 1")?;
+    writeln!(out, "guesses {}", guesses.len())?;
+    writeln!(out, "{}", guesses.iter().join(" "))?;
+    writeln!(out, "answers {}", answers.len())?;
+    writeln!(out, "{}", answers.iter().join(" "))?;
+    out.write_all(&feedback_matrix(guesses, answers))?;
+    out.flush()
+}
+
 // ---------- Main ----------
 
 fn main() {
-    println!("=== Wordle Solver ===");
-    println!("Fetching word lists...");
+    let mut options = match parse_args() {
+        Ok(o) => o,
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(2);
+        }
+    };
+
+    match options.command {
+        Command::Version => {
+            println!("wordle_word {}", env!("CARGO_PKG_VERSION"));
+            return;
+        }
+        Command::Sources => {
+            print_sources();
+            return;
+        }
+        _ => {}
+    }
+
+    // --status and --regex output is meant for other tools, so keep stdout to just that
+    let quiet = options.status || options.regex;
+    if !quiet {
+        println!("=== Wordle Solver ===");
+        println!("Fetching word lists...");
+    }
 
     let all = match all_words() {
         Ok(w) => w,
@@ -50,85 +1073,638 @@ fn main() {
         }
     };
 
-    let used = used_words();
+    let dated = used_words_dated();
+    let used: HashSet<String> = dated.iter().map(|a| a.word.clone()).collect();
+    // Pattern statistics below still learn from every past answer; only the exclusion
+    // narrows to the window
+    let excluded = match options.recent_days {
+        Some(days) => recent_answers(&dated, days, today()).unwrap_or_else(|| {
+            eprintln!("Warning: past answers have no dates, so all of them are excluded.");
+            used.clone()
+        }),
+        None => used.clone(),
+    };
+    if let Some(weight) = options.past_pattern_weight {
+        options.rank.past_answer_bias = Some(PastAnswerBias::new(&newest_first(&dated), weight));
+        if !quiet {
+            println!(
+                "Experimental past-answer pattern bias enabled (weight {}).",
+                weight
+            );
+        }
+    }
 
-    let available: HashSet<&String> = all.difference(&used).collect();
-    let freq_data = load_frequency_data(&available);
+    if let Some(weight) = options.answer_position_weight {
+        // Past answers are the best sample of the answer list's letter distribution
+        options.rank.answer_positions = Some(AnswerPositionBonus::new(&used, weight));
+    }
+
+    // The past-answer scrape can miss or misread entries, so --soft-exclude keeps past
+    // answers reachable and only ranks them last
+    let available: HashSet<&String> = if options.soft_exclude {
+        options.demoted = excluded.clone();
+        all.iter().collect()
+    } else {
+        all.difference(&excluded).collect()
+    };
+    let freq_data = load_frequency_data(&frequency_sources(), &available);
+    warn_if_no_commonality(options.rank.strategy, &freq_data.commonality);
+
+    if let Command::Audit = options.command {
+        run_audit(&all, &used, &freq_data.dictionary);
+        return;
+    }
 
     let mut candidate_strs: Vec<String> = available.iter().map(|s| s.to_string()).collect();
     let plurals_removed = filter_regular_plurals(&mut candidate_strs, &freq_data.dictionary);
+    let never_removed = filter_never_answers(&mut candidate_strs, &options.never_answers);
+    let before_known = candidate_strs.len();
+    if let Some(known) = &options.known_words {
+        filter_known_words(&mut candidate_strs, known);
+    }
     let candidate_set: HashSet<String> = candidate_strs.into_iter().collect();
 
-    println!(
-        "{} total words, {} past answers excluded, {} regular plurals filtered, {} candidates available.\n",
+    if !quiet {
+        println!(
+        "{} total words, {} past answers {}, {} regular plurals filtered, {} never-answers excluded, {} candidates available.\n",
         all.len(),
-        used.len(),
+        excluded.len(),
+        if options.soft_exclude { "ranked last" } else { "excluded" },
         plurals_removed,
+        never_removed,
         candidate_set.len()
     );
+        if !freq_data.commonality.is_empty() {
+            let refs: Vec<&String> = candidate_set.iter().collect();
+            println!(
+                "Commonality data covers {:.0}% of candidates.\n",
+                100.0 * commonality_coverage(&refs, &freq_data.commonality)
+            );
+        }
+        if options.known_words.is_some() {
+            println!(
+                "Known-words filter: {} candidates narrowed to {}.\n",
+                before_known,
+                candidate_set.len()
+            );
+        }
+    }
 
     let mut candidates: Vec<&String> = all.iter().filter(|w| candidate_set.contains(*w)).collect();
+
+    if let Some(opener) = &options.fixed_opener {
+        if !all.contains(opener) {
+            eprintln!("Fixed opener '{}' is not in the word list.", opener);
+            std::process::exit(2);
+        }
+    }
+
+    if let Command::Solve(answers) = &options.command {
+        run_solve(answers, &candidates, &freq_data.commonality, &options);
+        return;
+    }
+
+    if let Command::Tree(opener) = &options.command {
+        if !all.contains(opener) {
+            eprintln!("Opener '{}' is not in the word list.", opener);
+            std::process::exit(2);
+        }
+        run_tree(opener, &candidates, &freq_data.commonality, &options);
+        return;
+    }
+
+    if let Some(count) = options.boards {
+        run_boards(count, &candidates, &all, &options);
+        return;
+    }
+
+    if let Command::DumpMatrix(path) = &options.command {
+        let answers: Vec<&String> = candidates.iter().copied().sorted().collect();
+        let guesses: Vec<&String> = if options.matrix_all_guesses {
+            all.iter().sorted().collect()
+        } else {
+            answers.clone()
+        };
+        println!(
+            "Writing {} x {} feedback matrix to {}...",
+            guesses.len(),
+            answers.len(),
+            path.display()
+        );
+        if let Err(e) = write_matrix(path, &guesses, &answers) {
+            eprintln!("Failed to write {}: {}", path.display(), e);
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    let mut secret = if options.vs {
+        match read_secret(&all) {
+            Ok(word) => Some(word),
+            Err(e) => {
+                eprintln!("{}", e);
+                std::process::exit(2);
+            }
+        }
+    } else {
+        None
+    };
+    // A friend may well pick a past answer, so make sure the secret can be found
+    if let Some(word) = secret.as_ref().and_then(|s| all.get(s)) {
+        if !candidates.contains(&word) {
+            candidates.push(word);
+        }
+    }
+
+    let mut pool = candidates.clone();
     let mut state = GameState::new();
+    let mut guesses: Vec<(String, String)> = Vec::new();
+    let mut candidate_history = vec![candidates.len()];
+    // Letters set directly with `set`, as (0-based position, letter)
+    let mut known_letters: Vec<(usize, char)> = Vec::new();
+    let mut opener = options.fixed_opener.clone();
+
+    if let Some(path) = &options.load_path {
+        let save = match load_game(path) {
+            Ok(s) => s,
+            Err(e) => {
+                eprintln!("{}", e);
+                std::process::exit(1);
+            }
+        };
 
-    println!("Top starter suggestions:");
-    let ranked = rank_words(&candidates, &freq_data.commonality);
-    display_suggestions(&ranked, 15);
+        // Replay the history only to rebuild the narrowing counts
+        let mut replay = GameState::new();
+        let mut remaining = candidates.clone();
+        for (guess, feedback) in &save.guesses {
+            replay.update(guess, feedback);
+            remaining.retain(|w| replay.matches(w));
+            candidate_history.push(remaining.len());
+        }
 
+        state = save.state;
+        guesses = save.guesses;
+        candidates.retain(|w| state.matches(w));
+        if !quiet {
+            println!(
+                "Resumed game with {} guess(es) from {}.",
+                guesses.len(),
+                path.display()
+            );
+        }
+    }
+
+    for (guess, feedback) in &options.guesses {
+        state.update(guess, feedback);
+        candidates.retain(|w| state.matches(w));
+        candidate_history.push(candidates.len());
+        guesses.push((guess.clone(), feedback.clone()));
+    }
+
+    if options.status {
+        println!("{}", state.status_line(candidates.len()));
+        return;
+    }
+    if options.regex {
+        println!("{}", state.to_regex());
+        if let Some(caveat) = state.regex_caveat() {
+            println!("# {}", caveat);
+        }
+        return;
+    }
+
+    if !guesses.is_empty() {
+        opener = None;
+        println!("Constraints:");
+        state.display();
+        println!("  Remaining candidates: {}", candidates.len());
+        println!("\nTop suggestions:");
+        show_suggestions(&candidates, &all, &freq_data.commonality, &state, &options);
+    } else if opener.is_none() {
+        println!("Top starter suggestions:");
+        show_suggestions(&candidates, &all, &freq_data.commonality, &state, &options);
+    }
+
+    // Games solved and guesses used across them, for --continuous
+    let mut tally = (0, 0);
+    let mut new_game = false;
     loop {
+        if new_game {
+            new_game = false;
+            if secret.is_some() {
+                match read_secret(&all) {
+                    Ok(word) => {
+                        if let Some(word) = all.get(&word) {
+                            if !pool.contains(&word) {
+                                pool.push(word);
+                            }
+                        }
+                        secret = Some(word);
+                    }
+                    Err(e) => {
+                        eprintln!("{}", e);
+                        break;
+                    }
+                }
+            }
+            state = GameState::new();
+            guesses.clear();
+            known_letters.clear();
+            candidates = pool.clone();
+            candidate_history = vec![candidates.len()];
+            opener = options.fixed_opener.clone();
+            if opener.is_none() {
+                println!("\nTop starter suggestions:");
+                show_suggestions(&candidates, &all, &freq_data.commonality, &state, &options);
+            }
+        }
+
         println!();
-        print!("Enter guess (or 'q' to quit, '?' for help): ");
-        let guess = read_line().to_ascii_lowercase();
+        let guess = match &opener {
+            Some(word) => {
+                println!("Fixed opener: {}", word);
+                word.clone()
+            }
+            None => {
+                print!("Enter guess (or 'q' to quit, '?' for help): ");
+                read_line().to_ascii_lowercase()
+            }
+        };
 
         if guess == "q" {
+            if let Some(path) = &options.save_path {
+                save_game(path, &state, &guesses);
+            }
+            if let Some(secret) = &secret {
+                println!("The word was: {}", secret);
+            }
             break;
         }
         if guess == "?" {
             print_help();
             continue;
         }
+        if guess == "yellows" {
+            let groups = yellow_placements(&candidates, &state);
+            if groups.is_empty() {
+                println!("No unplaced yellow letters.");
+            }
+            for group in &groups {
+                println!(
+                    "  {} in position {}: {} candidate{} ({})",
+                    group.letter.to_ascii_uppercase(),
+                    group.position,
+                    group.words.len(),
+                    if group.words.len() == 1 { "" } else { "s" },
+                    group.words.iter().take(8).join(", ")
+                        + if group.words.len() > 8 { ", ..." } else { "" }
+                );
+            }
+            continue;
+        }
         if guess == "s" {
             println!("\nCurrent constraints:");
             state.display();
             println!("  Remaining candidates: {}", candidates.len());
+            if candidate_history.len() > 1 {
+                println!("  Narrowing: {}", candidate_history.iter().join(" -> "));
+            }
+            println!("  Alphabet: {}", alphabet_line(&state));
+            if candidates.len() > 1 {
+                println!(
+                    "  Estimated guesses to go: {:.1}",
+                    estimated_guesses(candidates.len())
+                );
+            }
+            if !freq_data.commonality.is_empty() {
+                println!(
+                    "  Commonality coverage: {:.0}%",
+                    100.0 * commonality_coverage(&candidates, &freq_data.commonality)
+                );
+            }
+            if candidates.len() > 1 {
+                println!(
+                    "  {}",
+                    letter_spread(&candidates, &state).summary(candidates.len())
+                );
+            }
+            let letters = letter_split_values(&candidates, &state);
+            if !letters.is_empty() {
+                println!(
+                    "  Letters worth testing: {}",
+                    letters
+                        .iter()
+                        .take(3)
+                        .map(|(c, _)| c.to_ascii_uppercase())
+                        .join(", ")
+                );
+            }
+            continue;
+        }
+        if let Some(mode) = guess.strip_prefix("mode ") {
+            match mode.trim().parse::<Strategy>() {
+                Ok(strategy) => {
+                    options.rank.strategy = strategy;
+                    println!("Ranking mode set to {}.", strategy.name());
+                    warn_if_no_commonality(strategy, &freq_data.commonality);
+                    if !candidates.is_empty() {
+                        println!("\nTop suggestions:");
+                        show_suggestions(
+                            &candidates,
+                            &all,
+                            &freq_data.commonality,
+                            &state,
+                            &options,
+                        );
+                    }
+                }
+                Err(e) => println!("{}", e),
+            }
             continue;
         }
 
-        if guess.len() != 5 || !guess.chars().all(|c| c.is_ascii_lowercase()) {
-            println!("Guess must be exactly 5 lowercase letters.");
+        if guess == "analyze" || guess.starts_with("analyze ") {
+            let word = match guess.split_whitespace().nth(1) {
+                Some(word) if is_valid_word(word) => Some(word.to_string()),
+                Some(_) => {
+                    println!("Usage: analyze [word]");
+                    continue;
+                }
+                None => top_pick(&candidates, &freq_data.commonality, &state, &options),
+            };
+            match word {
+                Some(word) => show_outcomes(&word, &candidates),
+                None => println!("No candidates to analyze."),
+            }
             continue;
         }
 
-        print!("Enter feedback (g/y/x): ");
-        let feedback = read_line().to_ascii_lowercase();
+        if let Some(words) = guess.strip_prefix("compare ") {
+            let words: Vec<&str> = words.split_whitespace().collect();
+            match words.as_slice() {
+                [a, b] if is_valid_word(a) && is_valid_word(b) => show_comparison(
+                    &compare_guess(a, &candidates, &freq_data.commonality),
+                    &compare_guess(b, &candidates, &freq_data.commonality),
+                ),
+                _ => println!("Usage: compare <word1> <word2>"),
+            }
+            continue;
+        }
 
-        if feedback.len() != 5 || !feedback.chars().all(|c| matches!(c, 'g' | 'y' | 'x')) {
-            println!("Feedback must be exactly 5 characters, each g, y, or x.");
+        if let Some(names) = guess.strip_prefix("fork ") {
+            let strategies: Result<Vec<Strategy>, String> =
+                names.split_whitespace().map(str::parse).collect();
+            match strategies.as_deref() {
+                Ok(&[a, b]) => show_fork(
+                    &candidates,
+                    &freq_data.commonality,
+                    &state,
+                    &options,
+                    [a, b],
+                ),
+                Ok(_) => println!("Usage: fork <strategy> <strategy>, e.g. fork freq entropy"),
+                Err(e) => println!("{}", e),
+            }
             continue;
         }
 
+        if let Some(spec) = guess.strip_prefix("set ") {
+            let Some((pos, letter)) = parse_known_letter(spec) else {
+                println!("Usage: set <n>=<letter>, e.g. set 1=c");
+                continue;
+            };
+            if let Err(e) = state.set_green(pos, letter) {
+                println!("Can't set that: {}.", e);
+                continue;
+            }
+            known_letters.push((pos, letter));
+            candidates.retain(|w| state.matches(w));
+            // Keep the starting pool size; after a guess, its count takes the letter in
+            if !guesses.is_empty() {
+                if let Some(count) = candidate_history.last_mut() {
+                    *count = candidates.len();
+                }
+            }
+
+            println!("\nConstraints:");
+            state.display();
+            println!("  Remaining candidates: {}", candidates.len());
+            println!("\nTop suggestions:");
+            show_suggestions(&candidates, &all, &freq_data.commonality, &state, &options);
+            continue;
+        }
+
+        if let Some(n) = guess.strip_prefix("back ") {
+            let to = match n.trim().parse::<usize>() {
+                Ok(to) if to <= guesses.len() => to,
+                _ => {
+                    println!("Usage: back <n>, where n is at most {}", guesses.len());
+                    continue;
+                }
+            };
+
+            // Replay the kept guesses from scratch so nothing from the dropped
+            // ones lingers in the constraints
+            guesses.truncate(to);
+            state = GameState::new();
+            for &(pos, letter) in &known_letters {
+                state.set_green(pos, letter).ok();
+            }
+            candidate_history = vec![pool.len()];
+            candidates = pool.clone();
+            candidates.retain(|w| state.matches(w));
+            for (guess, feedback) in &guesses {
+                state.update(guess, feedback);
+                candidates.retain(|w| state.matches(w));
+                candidate_history.push(candidates.len());
+            }
+            if let Some(path) = &options.save_path {
+                save_game(path, &state, &guesses);
+            }
+
+            println!("\nRolled back to after guess {}.", to);
+            println!("Constraints:");
+            state.display();
+            println!("  Remaining candidates: {}", candidates.len());
+            println!("\nTop suggestions:");
+            show_suggestions(&candidates, &all, &freq_data.commonality, &state, &options);
+            continue;
+        }
+
+        // "crane gyxxg" gives the guess and its feedback in one go
+        let (guess, inline_feedback) = match guess.split_whitespace().collect::<Vec<_>>()[..] {
+            [word, feedback] => (word.to_string(), Some(feedback.to_string())),
+            _ => (guess, None),
+        };
+
+        if let Err(e) = check_guess_input(&guess) {
+            println!("{}", e);
+            continue;
+        }
+
+        if !guesses.is_empty() && state.guess_value(&guess) == GuessValue::Wasteful {
+            println!(
+                "Note: '{}' only repeats what you already know -- it can't narrow the candidates.",
+                guess
+            );
+        }
+
+        let feedback = match &secret {
+            Some(secret) => {
+                if !all.contains(&guess) {
+                    println!("'{}' is not in the word list.", guess);
+                    continue;
+                }
+                let feedback = feedback_for(&guess, secret);
+                println!("Feedback: {}", feedback);
+                feedback
+            }
+            None => inline_feedback.unwrap_or_else(|| {
+                print!("Enter feedback (g/y/x/?): ");
+                read_line().to_ascii_lowercase()
+            }),
+        };
+
+        if let Err(e) = check_feedback_input(&feedback) {
+            println!("{}", e);
+            continue;
+        }
+        if let Some(repeat) = repeated_guess(&guesses, &guess, &feedback) {
+            println!("Warning: {}.", repeat);
+        }
+        opener = None;
+
         if feedback == "ggggg" {
             println!("Congratulations! You solved it: {}", guess);
-            break;
+            if !options.continuous || !another_game(&mut tally, guesses.len() + 1) {
+                break;
+            }
+            new_game = true;
+            continue;
         }
 
+        let before = candidates.len();
         state.update(&guess, &feedback);
         candidates.retain(|w| state.matches(w));
+        candidate_history.push(candidates.len());
+        let eliminated_nothing = ineffective_guess(before, candidates.len(), &feedback);
+        guesses.push((guess, feedback));
+        if let Some(path) = &options.save_path {
+            save_game(path, &state, &guesses);
+        }
 
+        if let Some(conflict) = find_letter_conflict(&guesses) {
+            println!("\nWarning: {}.", conflict);
+        }
+        if let Some(conflict) = known_letter_conflict(&known_letters, &guesses) {
+            println!("\nWarning: {}.", conflict);
+        }
+        if eliminated_nothing {
+            println!("\nThat guess didn't eliminate anything -- double-check the feedback.");
+        }
         println!("\nConstraints:");
         state.display();
         println!("  Remaining candidates: {}", candidates.len());
 
         if candidates.is_empty() {
             println!("\nNo words match these constraints. Double-check your feedback.");
+            if let Some((constraint, restored)) = state.diagnose_no_matches(&pool) {
+                println!(
+                    "Removing {} would restore {} candidate(s) -- double check that tile.",
+                    constraint, restored
+                );
+            }
             continue;
         }
-        if candidates.len() == 1 {
+        if candidates.len() == 1 && options.hints > HintLevel::CountOnly {
             println!("\nThe answer is: {}", candidates[0]);
-            break;
+            // Guessing it is the one guess still to come
+            if !options.continuous || !another_game(&mut tally, guesses.len() + 1) {
+                break;
+            }
+            new_game = true;
+            continue;
+        }
+
+        if guesses.len() >= options.max_guesses {
+            print!("\nYou're out of guesses ({} used)", guesses.len());
+            match most_likely_answer(&candidates, &freq_data.commonality)
+                .filter(|_| options.hints.shows_analysis())
+            {
+                Some(word) => println!(" -- the most likely answer is {}.", word),
+                None => println!("."),
+            }
+        }
+
+        if options.hints.shows_analysis() {
+            let guesses_left = options.max_guesses.saturating_sub(guesses.len());
+            if (1..=2).contains(&guesses_left) && candidates.len() <= ENDGAME_SEARCH_LIMIT {
+                match guaranteed_solution(&candidates, guesses_left) {
+                    Some(word) => println!(
+                        "\nGuaranteed win with {} guess(es) left: guess {}",
+                        guesses_left, word
+                    ),
+                    None => println!("\nNo guaranteed win with {} guess(es) left.", guesses_left),
+                }
+            }
+
+            if let [a, b] = candidates[..] {
+                // Lead with the more common word, which is the likelier answer
+                let common = |w: &String| freq_data.commonality.get(w).copied().unwrap_or(0.0);
+                let (a, b) = if common(b) > common(a) {
+                    (b, a)
+                } else {
+                    (a, b)
+                };
+                if let Some(word) = disambiguating_guess(a, b, &[]) {
+                    println!(
+                        "\nTwo candidates left: {} or {}. Guess {} -- its feedback tells them apart.",
+                        a, b, word
+                    );
+                }
+            }
+            if all_anagrams(&candidates) {
+                let guess_pool: Vec<&String> = all.iter().sorted().collect();
+                if let Some((probe, worst)) = anagram_probe(&candidates, &guess_pool) {
+                    println!(
+                        "\nAll {} candidates are anagrams of each other ({}), so only the letter order is left to find. Guess {}: it leaves at most {}{}.",
+                        candidates.len(),
+                        candidates.iter().sorted().join(", "),
+                        probe,
+                        worst,
+                        if candidates.contains(&probe) {
+                            " and could be the answer itself"
+                        } else {
+                            ""
+                        }
+                    );
+                }
+            }
+            if candidates.len() <= LIKELY_ANSWER_LIMIT {
+                if let Some(probs) = answer_probabilities(&candidates, &freq_data.commonality) {
+                    let (word, p) = probs[0];
+                    // Only call a familiar word the likely answer
+                    if options.rank.is_common(word, &freq_data.commonality) {
+                        println!("\nThe answer is likely {} ({:.0}%)", word, p * 100.0);
+                    }
+                }
+            }
+            if options.trickiest && candidates.len() <= SAFE_GUESS_LIMIT {
+                if let Some((word, left)) = hardest_candidate(&candidates) {
+                    println!(
+                        "\nThe trickiest possible answer right now is {} (the best guess for it still leaves {}).",
+                        word, left
+                    );
+                }
+            }
+            if candidates.len() <= SAFE_GUESS_LIMIT {
+                if let Some(word) = best_candidate_guess(&candidates, &freq_data.commonality) {
+                    println!("\nBest safe guess (could be the answer): {}", word);
+                }
+            }
         }
 
         println!("\nTop suggestions:");
-        let ranked = rank_words(&candidates, &freq_data.commonality);
-        display_suggestions(&ranked, 15);
+        show_suggestions(&candidates, &all, &freq_data.commonality, &state, &options);
     }
 }